@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+
+use widestring::{U16Str, U16String};
+
+use crate::interface::{ParamValue, ParamValueRef, PlatformValue};
+
+use super::{
+    memory_manager::{AllocationError, MemoryManager},
+    provided_types::TVariant,
+};
+
+/// Version of Native API, reported by the AddIn with `GetInfo`. Versions
+/// differ only in strings, that the AddIn passes to 1C platform in `TVariant`
+/// values: 2.0 uses UTF-16 `WStr`, while 1.0 hosts (e.g. older OneScript
+/// builds) expect single-byte `PStr` (`char*`) strings, in encoding, set with
+/// [`PStrEncoding`]. Strings of both kinds are accepted from the host in any
+/// version.
+///
+/// All differences between versions are kept here, the rest of `ffi` writes
+/// values with [`ApiVersion::write_value`] and [`ApiVersion::write_platform_value`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiVersion {
+    /// Native API 1.0, `GetInfo` returns `1000`
+    V1,
+    /// Native API 2.0, `GetInfo` returns `2000`
+    #[default]
+    V2,
+}
+
+impl ApiVersion {
+    /// Value, returned from `GetInfo`
+    pub const fn info(self) -> u16 {
+        match self {
+            Self::V1 => 1000,
+            Self::V2 => 2000,
+        }
+    }
+
+    /// Version for value, returned from [`crate::interface::AddInWrapper::get_info`].
+    /// Values below `2000` mean 1.0
+    pub const fn from_info(info: u16) -> Self {
+        if info < 2000 { Self::V1 } else { Self::V2 }
+    }
+
+    /// Writes `value` to `variant` in the form, expected by the host of this
+    /// version, see [`TVariant::update_from_return`]. `encoding` is used for
+    /// `PStr` strings of 1.0
+    pub fn write_value(
+        self,
+        encoding: PStrEncoding,
+        variant: &mut TVariant,
+        mem_mngr: &MemoryManager,
+        value: &ParamValue,
+    ) -> Result<(), AllocationError> {
+        match (self, value) {
+            (Self::V1, ParamValue::String(v)) => {
+                let v = encoding.encode(v);
+                unsafe { variant.update_to_pstr(mem_mngr, &v) }.map(|_| ())
+            }
+            _ => variant.update_from_return(mem_mngr, value),
+        }
+    }
+
+    /// Same as [`ApiVersion::write_value`] for [`PlatformValue`]. 1.0 hosts
+    /// do not read UTF-16, so [`super::platform_buf::PlatformStr`] is
+    /// converted then
+    pub fn write_platform_value(
+        self,
+        encoding: PStrEncoding,
+        variant: &mut TVariant,
+        mem_mngr: &MemoryManager,
+        value: PlatformValue,
+    ) -> Result<(), AllocationError> {
+        match (self, value) {
+            (Self::V1, PlatformValue::String(v)) => {
+                let v = encoding.encode(v.as_ustr());
+                unsafe { variant.update_to_pstr(mem_mngr, &v) }.map(|_| ())
+            }
+            (Self::V1, PlatformValue::Value(v)) => {
+                self.write_value(encoding, variant, mem_mngr, &v)
+            }
+            (_, value) => variant.update_from_platform(mem_mngr, value),
+        }
+    }
+}
+
+/// Encoding of single-byte `PStr` strings. Native API 1.0 passes `char*` as
+/// is, so it is the encoding of the host: UTF-8 for OneScript and hosts on
+/// Linux, ANSI code page for 1C platform on Windows, e.g. Windows-1251 for
+/// Cyrillic installations. Reported by
+/// [`crate::interface::AddInWrapper::pstr_encoding`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PStrEncoding {
+    #[default]
+    Utf8,
+    /// Windows-1251 (Cyrillic ANSI code page)
+    Windows1251,
+}
+
+/// Characters of Windows-1251 bytes `0x80`-`0xBF`, bytes `0xC0`-`0xFF` are
+/// `А`-`я`. Undefined `0x98` is mapped to `U+0098`, as WHATWG does
+const WINDOWS_1251_HIGH: [u16; 64] = [
+    0x0402, 0x0403, 0x201A, 0x0453, 0x201E, 0x2026, 0x2020, 0x2021, //
+    0x20AC, 0x2030, 0x0409, 0x2039, 0x040A, 0x040C, 0x040B, 0x040F, //
+    0x0452, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014, //
+    0x0098, 0x2122, 0x0459, 0x203A, 0x045A, 0x045C, 0x045B, 0x045F, //
+    0x00A0, 0x040E, 0x045E, 0x0408, 0x00A4, 0x0490, 0x00A6, 0x00A7, //
+    0x0401, 0x00A9, 0x0404, 0x00AB, 0x00AC, 0x00AD, 0x00AE, 0x0407, //
+    0x00B0, 0x00B1, 0x0406, 0x0456, 0x0491, 0x00B5, 0x00B6, 0x00B7, //
+    0x0451, 0x2116, 0x0454, 0x00BB, 0x0458, 0x0405, 0x0455, 0x0457, //
+];
+
+impl PStrEncoding {
+    /// Encodes `s` for `PStr`. Characters, that the encoding does not have,
+    /// are replaced with `?`
+    pub fn encode(self, s: &U16Str) -> Vec<u8> {
+        match self {
+            Self::Utf8 => s.to_string_lossy().into_bytes(),
+            Self::Windows1251 => s
+                .as_slice()
+                .iter()
+                .map(|&c| match c {
+                    0x0000..=0x007F => c as u8,
+                    0x0410..=0x044F => (c - 0x0410 + 0xC0) as u8,
+                    _ => WINDOWS_1251_HIGH
+                        .iter()
+                        .position(|&high| high == c)
+                        .map_or(b'?', |i| 0x80 + i as u8),
+                })
+                .collect(),
+        }
+    }
+
+    /// Decodes `PStr` bytes. Invalid UTF-8 is replaced with `U+FFFD`
+    pub fn decode(self, bytes: &[u8]) -> U16String {
+        match self {
+            Self::Utf8 => U16String::from_str(&String::from_utf8_lossy(bytes)),
+            Self::Windows1251 => bytes
+                .iter()
+                .map(|&b| match b {
+                    0x00..=0x7F => b as u16,
+                    0x80..=0xBF => WINDOWS_1251_HIGH[(b - 0x80) as usize],
+                    0xC0..=0xFF => b as u16 - 0xC0 + 0x0410,
+                })
+                .collect::<Vec<_>>()
+                .into(),
+        }
+    }
+
+    /// Reads `variant` as [`ParamValueRef::from`] does, decoding `PStr` with
+    /// this encoding
+    pub fn read_value(self, variant: &TVariant) -> ParamValueRef<'_> {
+        match variant.as_pstr() {
+            Some(bytes) if self != Self::Utf8 => {
+                ParamValueRef::String(Cow::Owned(self.decode(bytes)))
+            }
+            _ => ParamValueRef::from(variant),
+        }
+    }
+}
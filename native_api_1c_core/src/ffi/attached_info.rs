@@ -0,0 +1,33 @@
+use std::ffi::c_int;
+
+use super::AttachType;
+
+/// VTable for AttachedInfo object, derived from Native API interface. See original
+/// C++ implementation in [example project](https://its.1c.ru/db/files/1CITS/EXE/VNCOMPS/VNCOMPS.zip)
+/// from 1C documentation. Returned `AttachedType` is kept as `c_int`, so that
+/// values unknown to this crate are not undefined behavior
+#[repr(C)]
+struct AttachedInfoVTable {
+    get_attached_info: unsafe extern "system" fn(&AttachedInfo) -> c_int,
+}
+
+/// AttachedInfo object, used to get the scheme, 1C platform actually chose
+/// to attach the AddIn. Obtained with `Connection::get_interface`
+#[repr(C)]
+pub struct AttachedInfo {
+    vptr: &'static AttachedInfoVTable,
+}
+
+impl AttachedInfo {
+    /// Equivalent to `GetAttachedInfo` from Native API interface
+    /// # Returns
+    /// `Option<AttachType>` - `AttachType::Isolated` or
+    /// `AttachType::NotIsolated`, `None` if value is unknown
+    pub fn get_attached_info(&self) -> Option<AttachType> {
+        match unsafe { (self.vptr.get_attached_info)(self) } {
+            0 => Some(AttachType::Isolated),
+            1 => Some(AttachType::NotIsolated),
+            _ => None,
+        }
+    }
+}
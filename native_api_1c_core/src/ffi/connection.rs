@@ -1,12 +1,24 @@
-use std::ffi::{c_long, c_ushort};
-use widestring::U16CString;
+use std::{
+    ffi::{c_long, c_ushort, c_void},
+    fmt,
+    ptr::{self, NonNull},
+};
+use widestring::{U16CStr, U16CString};
 use super::{
-    provided_types::TVariant
+    attached_info::AttachedInfo,
+    memory_manager::MemoryManager,
+    msg_box::MsgBox,
+    platform_info::{AppInfo, PlatformInfo},
+    platform_capabilities,
+    provided_types::TVariant,
+    AppCapabilities, AttachType,
 };
+use crate::interface::{AddInError, ParamValue};
 
 /// Message codes that can be used in `Connection::add_error` method
 /// to specify message type.
 /// See [1C documentation](https://its.1c.ru/db/content/metod8dev/src/developers/platform/i8103221.htm#_com_infomessage)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MessageCode {
     /// Error without icon
     None = 1000,
@@ -30,6 +42,49 @@ pub enum MessageCode {
     DialogFail = 1009,
 }
 
+/// Additional interfaces of 1C platform, that can be requested with
+/// `Connection::get_interface`. Equivalent to `IAddInDefBaseEx::Interfaces`
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interface {
+    /// `IMsgBox`, see [MsgBox](super::msg_box::MsgBox)
+    MsgBox = 0,
+    /// `IPlatformInfo`, see [PlatformInfo](super::platform_info::PlatformInfo)
+    PlatformInfo,
+    /// `IAndroidComponentHelper`
+    #[cfg(target_os = "android")]
+    AndroidComponentHelper,
+    /// `IAttachedInfo`, see [AttachedInfo](super::attached_info::AttachedInfo)
+    AttachedInfo,
+}
+
+/// Error of reading a value from 1C user profile with `Connection::read`
+/// # Fields
+/// * `code` - error code, returned by 1C platform
+/// * `description` - error description, returned by 1C platform, if any
+#[derive(Clone, Debug)]
+pub struct ProfileReadError {
+    pub code: c_long,
+    pub description: Option<String>,
+}
+
+impl fmt::Display for ProfileReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.description {
+            Some(description) => write!(f, "{description} ({})", self.code),
+            None => write!(f, "profile read error ({})", self.code),
+        }
+    }
+}
+
+impl std::error::Error for ProfileReadError {}
+
+impl From<ProfileReadError> for AddInError {
+    fn from(err: ProfileReadError) -> Self {
+        AddInError::from_error(err)
+    }
+}
+
 /// VTable for Connection object, derived from Native API interface. See original
 /// C++ implementation in [example project](https://its.1c.ru/db/files/1CITS/EXE/VNCOMPS/VNCOMPS.zip)
 /// from 1C documentation
@@ -49,7 +104,7 @@ struct ConnectionVTable {
         &Connection,
         *mut u16,
         &mut TVariant,
-        c_long,
+        *mut c_long,
         *mut *mut u16,
     ) -> bool,
     write:
@@ -68,6 +123,9 @@ struct ConnectionVTable {
     clean_event_buffer: unsafe extern "system" fn(&Connection),
     set_status_line: unsafe extern "system" fn(&Connection, *mut u16) -> bool,
     reset_status_line: unsafe extern "system" fn(&Connection),
+    /// only in `IAddInDefBaseEx`, see `Connection::get_interface`
+    get_interface:
+        unsafe extern "system" fn(&Connection, Interface) -> *mut c_void,
 }
 
 /// Connection object, used to communicate with 1C platform after the AddIn is loaded
@@ -89,6 +147,25 @@ impl Connection {
         code: MessageCode,
         source: &str,
         description: &str,
+    ) -> bool {
+        self.add_error_with_scode(code, source, description, 0)
+    }
+
+    /// Equivalent to `AddError` from Native API interface, same as `add_error`, but
+    /// also passes error code
+    /// # Arguments
+    /// * `code` - message code, see [MessageCode](enum.MessageCode)
+    /// * `source` - source of the error
+    /// * `description` - description of the error
+    /// * `scode` - error code, non-zero value makes 1C platform raise an exception
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn add_error_with_scode(
+        &self,
+        code: MessageCode,
+        source: &str,
+        description: &str,
+        scode: c_long,
     ) -> bool {
         unsafe {
             let source_wstr = U16CString::from_str_truncate(source);
@@ -98,11 +175,118 @@ impl Connection {
                 code as u16,
                 source_wstr.as_ptr(),
                 description_wstr.as_ptr(),
-                0,
+                scode,
             )
         }
     }
 
+    /// Reports `AddInError` to 1C platform with `AddError`, using its message code,
+    /// full description and error code
+    /// # Arguments
+    /// * `source` - source of the error
+    /// * `error` - error to report
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn report_error(&self, source: &str, error: &AddInError) -> bool {
+        self.add_error_with_scode(
+            error.code(),
+            source,
+            &error.description(),
+            error.scode(),
+        )
+    }
+
+    /// Equivalent to `RegisterProfileAs` from Native API interface and is used to set
+    /// the name of 1C user profile section, where values are stored with `read` and
+    /// `write` methods
+    /// # Arguments
+    /// * `profile_name` - name of the profile section, usually specific to the AddIn
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn register_profile_as(&self, profile_name: &str) -> bool {
+        unsafe {
+            let mut profile_name_wstr = U16CString::from_str_truncate(profile_name);
+            (self.vptr1.register_profile_as)(self, profile_name_wstr.as_mut_ptr())
+        }
+    }
+
+    /// Equivalent to `Read` from Native API interface and is used to load a value,
+    /// stored in 1C user profile with `write`
+    /// # Arguments
+    /// * `mem` - memory manager, used to free memory, allocated by 1C platform
+    /// * `name` - name of the value in profile
+    /// # Returns
+    /// `Result<ParamValue, ProfileReadError>` - stored value or error, returned by 1C platform
+    pub fn read(
+        &self,
+        mem: &MemoryManager,
+        name: &str,
+    ) -> Result<ParamValue, ProfileReadError> {
+        let mut name_wstr = U16CString::from_str_truncate(name);
+        let mut variant = TVariant::default();
+        let mut error_code: c_long = 0;
+        let mut error_description: *mut u16 = ptr::null_mut();
+
+        let read_result = unsafe {
+            (self.vptr1.read)(
+                self,
+                name_wstr.as_mut_ptr(),
+                &mut variant,
+                &mut error_code,
+                &mut error_description,
+            )
+        };
+
+        let description = if error_description.is_null() {
+            None
+        } else {
+            let description =
+                unsafe { U16CStr::from_ptr_str(error_description) }
+                    .to_string_lossy();
+            mem.free_memory(&mut error_description.cast::<c_void>());
+            Some(description)
+        };
+
+        if !read_result {
+            return Err(ProfileReadError {
+                code: error_code,
+                description,
+            });
+        }
+
+        let value = ParamValue::from(&variant);
+        unsafe { variant.free_data(mem) };
+        Ok(value)
+    }
+
+    /// Equivalent to `Write` from Native API interface and is used to store a value
+    /// in 1C user profile
+    /// # Arguments
+    /// * `mem` - memory manager, used to pass string and blob values to 1C platform
+    /// * `name` - name of the value in profile
+    /// * `value` - value to store
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn write(
+        &self,
+        mem: &MemoryManager,
+        name: &str,
+        value: &ParamValue,
+    ) -> bool {
+        let mut name_wstr = U16CString::from_str_truncate(name);
+        let mut variant = TVariant::default();
+        if variant.update_from_return(mem, value).is_err() {
+            return false;
+        }
+
+        let write_result = unsafe {
+            (self.vptr1.write)(self, name_wstr.as_mut_ptr(), &mut variant)
+        };
+
+        unsafe { variant.free_data(mem) };
+        write_result
+    }
+
     /// Equivalent to `ExternalEvent` from Native API interface and is used to send an external event to the 1C platform
     /// # Arguments
     /// * `caller` - name of the event caller
@@ -124,6 +308,25 @@ impl Connection {
         }
     }
 
+    /// Equivalent to `SetStatusLine` from Native API interface and is used to show a message
+    /// in the status bar of 1C client
+    /// # Arguments
+    /// * `text` - message to show
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn set_status_line(&self, text: &str) -> bool {
+        unsafe {
+            let mut text_wstr = U16CString::from_str_truncate(text);
+            (self.vptr1.set_status_line)(self, text_wstr.as_mut_ptr())
+        }
+    }
+
+    /// Equivalent to `ResetStatusLine` from Native API interface and is used to clear
+    /// the message, set with `set_status_line`
+    pub fn reset_status_line(&self) {
+        unsafe { (self.vptr1.reset_status_line)(self) }
+    }
+
     /// Equivalent to `SetEventBufferDepth` from Native API interface
     /// # Arguments
     /// * `depth` - new event buffer depth
@@ -137,4 +340,59 @@ impl Connection {
     pub fn get_event_buffer_depth(&self) -> c_long {
         unsafe { (self.vptr1.get_event_buffer_depth)(self) }
     }
+
+    /// Equivalent to `CleanEventBuffer` from Native API interface and is used to
+    /// discard all external events, that are not yet processed by 1C platform
+    pub fn clean_event_buffer(&self) {
+        unsafe { (self.vptr1.clean_event_buffer)(self) }
+    }
+
+    /// Equivalent to `GetInterface` from Native API interface (`IAddInDefBaseEx`)
+    /// and is used to request additional interfaces of 1C platform
+    /// # Arguments
+    /// * `interface` - requested interface
+    /// # Returns
+    /// `Option<NonNull<c_void>>` - pointer to the interface object, `None` if
+    /// it is not provided by 1C platform in current context. Also `None`, if
+    /// platform did not negotiate capabilities with `SetPlatformCapabilities`:
+    /// then it passes `IAddInDefBase`, that has no `GetInterface` method
+    pub fn get_interface(&self, interface: Interface) -> Option<NonNull<c_void>> {
+        if platform_capabilities() < AppCapabilities::V1 {
+            return None;
+        }
+        NonNull::new(unsafe { (self.vptr1.get_interface)(self, interface) })
+    }
+
+    /// Requests `IMsgBox` interface, used to show modal dialogs
+    /// # Returns
+    /// `Option<&MsgBox>` - MsgBox object, `None` if dialogs are not available,
+    /// e.g. on server
+    pub fn msg_box(&self) -> Option<&MsgBox> {
+        self.get_interface(Interface::MsgBox)
+            .map(|ptr| unsafe { ptr.cast::<MsgBox>().as_ref() })
+    }
+
+    /// Requests `IPlatformInfo` interface and reads information about 1C
+    /// platform: version, type of application and user agent
+    /// # Returns
+    /// `Option<AppInfo>` - information about 1C platform, `None` if it is not
+    /// provided
+    pub fn platform_info(&self) -> Option<AppInfo> {
+        self.get_interface(Interface::PlatformInfo)
+            .and_then(|ptr| unsafe {
+                ptr.cast::<PlatformInfo>().as_ref().get_platform_info()
+            })
+    }
+
+    /// Requests `IAttachedInfo` interface and reads the scheme, 1C platform
+    /// actually chose to attach the AddIn. Available from `init` onward
+    /// # Returns
+    /// `Option<AttachType>` - `AttachType::Isolated` or `AttachType::NotIsolated`,
+    /// `None` if it is not provided
+    pub fn attach_type(&self) -> Option<AttachType> {
+        self.get_interface(Interface::AttachedInfo)
+            .and_then(|ptr| unsafe {
+                ptr.cast::<AttachedInfo>().as_ref().get_attached_info()
+            })
+    }
 }
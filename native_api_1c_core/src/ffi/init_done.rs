@@ -22,7 +22,8 @@ unsafe extern "system" fn init<T: AddInWrapper>(
     interface: &'static Connection,
 ) -> bool {
     let component = unsafe { this.get_component() };
-    component.addin.init(interface)
+    component.connection_ptr = Some(interface);
+    component.guard("Init", false, |component| component.addin.init(interface))
 }
 
 unsafe extern "system" fn set_mem_manager<T: AddInWrapper>(
@@ -31,19 +32,23 @@ unsafe extern "system" fn set_mem_manager<T: AddInWrapper>(
 ) -> bool {
     let component = unsafe { this.get_component() };
     component.memory_manager_ptr = Some(mem);
-    true
+    component.guard("SetMemManager", false, |component| {
+        component.addin.set_mem_manager(mem)
+    })
 }
 
 unsafe extern "system" fn get_info<T: AddInWrapper>(
     this: &mut This<T>,
 ) -> c_long {
     let component = unsafe { this.get_component() };
-    component.addin.get_info() as c_long
+    component.guard("GetInfo", 0, |component| {
+        component.addin.get_info() as c_long
+    })
 }
 
 unsafe extern "system" fn done<T: AddInWrapper>(this: &mut This<T>) {
     let component = unsafe { this.get_component() };
-    component.addin.done()
+    component.guard("Done", (), |component| component.addin.done())
 }
 
 impl<T: AddInWrapper> Default for InitDoneBaseVTable<T> {
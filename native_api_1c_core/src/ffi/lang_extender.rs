@@ -1,7 +1,13 @@
 use widestring::U16CStr;
 
-use super::{offset, provided_types::TVariant};
-use crate::interface::{AddInWrapper, ParamValue, ParamValues};
+use super::{
+    api_version::{ApiVersion, PStrEncoding},
+    memory_manager::MemoryManager, offset,
+    provided_types::TVariant, Component,
+};
+use crate::interface::{
+    AddInError, AddInWrapper, ParamValue, ParamValuesRef,
+};
 use std::{
     ffi::c_long,
     ptr::{self},
@@ -54,38 +60,59 @@ pub struct LanguageExtenderBaseVTable<T: AddInWrapper> {
     ) -> bool,
 }
 
+/// Reports error, returned by AddIn, to 1C platform. Name of the AddIn method
+/// or property is used as error source, if it is known
+fn report_error<T: AddInWrapper>(
+    component: &Component<T>,
+    source: Option<&U16CStr>,
+    fallback_source: &str,
+    error: &AddInError,
+) {
+    let Some(connection) = component.connection_ptr else {
+        return;
+    };
+    let source = source
+        .map(|source| source.to_string_lossy())
+        .unwrap_or_else(|| fallback_source.to_string());
+    connection.report_error(&source, error);
+}
+
 unsafe extern "system" fn register_extension_as<T: AddInWrapper>(
     this: &mut This<T>,
     name: *mut *mut u16,
 ) -> bool {
     let component = unsafe { this.get_component() };
-    let Some(allocator) = component.memory_manager_ptr else {
-        return false;
-    };
-
-    let extension_name = component.addin.register_extension_as();
-    let extension_name_len = extension_name.as_slice_with_nul().len();
-
-    let Ok(ptr) = allocator.alloc_str(extension_name_len) else {
-        return false;
-    };
-    unsafe { 
-        ptr::copy_nonoverlapping(
-            extension_name.as_ptr(),
-            ptr.as_ptr(),
-            extension_name_len,
-        );
-        *name = ptr.as_ptr();
-    }
+    component.guard("RegisterExtensionAs", false, |component| {
+        let Some(allocator) = component.memory_manager_ptr else {
+            return false;
+        };
+
+        let extension_name = component.addin.register_extension_as();
+        let extension_name_len = extension_name.as_slice_with_nul().len();
+
+        let Ok(ptr) = allocator.alloc_str(extension_name_len) else {
+            return false;
+        };
+        unsafe {
+            ptr::copy_nonoverlapping(
+                extension_name.as_ptr(),
+                ptr.as_ptr(),
+                extension_name_len,
+            );
+            *name = ptr.as_ptr();
+        }
 
-    true
+        true
+    })
 }
 
 unsafe extern "system" fn get_n_props<T: AddInWrapper>(
     this: &mut This<T>,
 ) -> c_long {
     let component = unsafe { this.get_component() };
-    component.addin.get_n_props() as c_long
+    component.guard("GetNProps", 0, |component| {
+        component.addin.get_n_props() as c_long
+    })
 }
 
 unsafe extern "system" fn find_prop<T: AddInWrapper>(
@@ -94,10 +121,12 @@ unsafe extern "system" fn find_prop<T: AddInWrapper>(
 ) -> c_long {
     let component = unsafe { this.get_component() };
     let name = unsafe { U16CStr::from_ptr_str(name) };
-    match component.addin.find_prop(name) {
-        Some(i) => i as c_long,
-        None => -1,
-    }
+    component.guard("FindProp", -1, |component| {
+        match component.addin.find_prop(name) {
+            Some(i) => i as c_long,
+            None => -1,
+        }
+    })
 }
 
 unsafe extern "system" fn get_prop_name<T: AddInWrapper>(
@@ -106,24 +135,30 @@ unsafe extern "system" fn get_prop_name<T: AddInWrapper>(
     alias: c_long,
 ) -> *const u16 {
     let component = unsafe { this.get_component() };
-    let Some(allocator) = component.memory_manager_ptr else {
-        return ptr::null();
-    };
-    let Some(prop_name) =
-        component.addin.get_prop_name(num as usize, alias as usize)
-    else {
-        return ptr::null();
-    };
-    let prop_name_len = prop_name.as_slice_with_nul().len();
-
-    let Ok(ptr) = allocator.alloc_str(prop_name_len) else {
-        return ptr::null();
-    };
-    unsafe {
-        ptr::copy_nonoverlapping(prop_name.as_ptr(), ptr.as_ptr(), prop_name_len);
-    }
+    component.guard("GetPropName", ptr::null(), |component| {
+        let Some(allocator) = component.memory_manager_ptr else {
+            return ptr::null();
+        };
+        let Some(prop_name) =
+            component.addin.get_prop_name(num as usize, alias as usize)
+        else {
+            return ptr::null();
+        };
+        let prop_name_len = prop_name.as_slice_with_nul().len();
+
+        let Ok(ptr) = allocator.alloc_str(prop_name_len) else {
+            return ptr::null();
+        };
+        unsafe {
+            ptr::copy_nonoverlapping(
+                prop_name.as_ptr(),
+                ptr.as_ptr(),
+                prop_name_len,
+            );
+        }
 
-    ptr.as_ptr()
+        ptr.as_ptr()
+    })
 }
 
 unsafe extern "system" fn get_prop_val<T: AddInWrapper>(
@@ -132,18 +167,29 @@ unsafe extern "system" fn get_prop_val<T: AddInWrapper>(
     val: &mut TVariant,
 ) -> bool {
     let component = unsafe { this.get_component() };
-    let Some(mem_mngr) = component.memory_manager_ptr else {
-        return false;
-    };
-
-    let prop_val_result = component.addin.get_prop_val(num as usize);
-    match prop_val_result {
-        Ok(prop_val) => {
-            val.update_from_return(mem_mngr, &prop_val);
-            true
+    component.guard("GetPropVal", false, |component| {
+        let Some(mem_mngr) = component.memory_manager_ptr else {
+            return false;
+        };
+
+        let api_version = component.api_version();
+        let encoding = component.addin.pstr_encoding();
+        let prop_val_result = component
+            .addin
+            .get_prop_val(num as usize)
+            .and_then(|prop_val| {
+                api_version.write_value(encoding, val, mem_mngr, &prop_val)?;
+                Ok(())
+            });
+        match prop_val_result {
+            Ok(()) => true,
+            Err(err) => {
+                let prop_name = component.addin.get_prop_name(num as usize, 0);
+                report_error(component, prop_name, "GetPropVal", &err);
+                false
+            }
         }
-        Err(_) => false,
-    }
+    })
 }
 
 unsafe extern "system" fn set_prop_val<T: AddInWrapper>(
@@ -151,18 +197,29 @@ unsafe extern "system" fn set_prop_val<T: AddInWrapper>(
     num: c_long,
     val: &TVariant,
 ) -> bool {
-    unsafe { this.get_component() }
-        .addin
-        .set_prop_val(num as usize, val.into())
-        .is_ok()
+    let component = unsafe { this.get_component() };
+    component.guard("SetPropVal", false, |component| {
+        let val = component.addin.pstr_encoding().read_value(val).into_owned();
+        let set_result = component.addin.set_prop_val(num as usize, val);
+        match set_result {
+            Ok(()) => true,
+            Err(err) => {
+                let prop_name = component.addin.get_prop_name(num as usize, 0);
+                report_error(component, prop_name, "SetPropVal", &err);
+                false
+            }
+        }
+    })
 }
 
 unsafe extern "system" fn is_prop_readable<T: AddInWrapper>(
     this: &mut This<T>,
     num: c_long,
 ) -> bool {
-    unsafe { this.get_component() }
-        .addin.is_prop_readable(num as usize)
+    let component = unsafe { this.get_component() };
+    component.guard("IsPropReadable", false, |component| {
+        component.addin.is_prop_readable(num as usize)
+    })
 }
 
 unsafe extern "system" fn is_prop_writable<T: AddInWrapper>(
@@ -170,14 +227,18 @@ unsafe extern "system" fn is_prop_writable<T: AddInWrapper>(
     num: c_long,
 ) -> bool {
     let component = unsafe { this.get_component() };
-    component.addin.is_prop_writable(num as usize)
+    component.guard("IsPropWritable", false, |component| {
+        component.addin.is_prop_writable(num as usize)
+    })
 }
 
 unsafe extern "system" fn get_n_methods<T: AddInWrapper>(
     this: &mut This<T>,
 ) -> c_long {
     let component = unsafe { this.get_component() };
-    component.addin.get_n_methods() as c_long
+    component.guard("GetNMethods", 0, |component| {
+        component.addin.get_n_methods() as c_long
+    })
 }
 
 unsafe extern "system" fn find_method<T: AddInWrapper>(
@@ -186,10 +247,12 @@ unsafe extern "system" fn find_method<T: AddInWrapper>(
 ) -> c_long {
     let component = unsafe { this.get_component() };
     let name = unsafe { U16CStr::from_ptr_str(name) };
-    match component.addin.find_method(name) {
-        Some(i) => i as c_long,
-        None => -1,
-    }
+    component.guard("FindMethod", -1, |component| {
+        match component.addin.find_method(name) {
+            Some(i) => i as c_long,
+            None => -1,
+        }
+    })
 }
 
 unsafe extern "system" fn get_method_name<T: AddInWrapper>(
@@ -198,30 +261,32 @@ unsafe extern "system" fn get_method_name<T: AddInWrapper>(
     alias: c_long,
 ) -> *const u16 {
     let component = unsafe { this.get_component() };
-    let Some(allocator) = component.memory_manager_ptr else {
-        return ptr::null();
-    };
-    let Some(method_name) = component
-        .addin
-        .get_method_name(num as usize, alias as usize)
-    else {
-        return ptr::null();
-    };
-    let method_name_len = method_name.as_slice_with_nul().len();
-
-    let Ok(ptr) = allocator.alloc_str(method_name_len) else {
-        return ptr::null();
-    };
-
-    unsafe {
-        ptr::copy_nonoverlapping(
-            method_name.as_ptr(),
-            ptr.as_ptr(),
-            method_name_len,
-        );
-    }
+    component.guard("GetMethodName", ptr::null(), |component| {
+        let Some(allocator) = component.memory_manager_ptr else {
+            return ptr::null();
+        };
+        let Some(method_name) = component
+            .addin
+            .get_method_name(num as usize, alias as usize)
+        else {
+            return ptr::null();
+        };
+        let method_name_len = method_name.as_slice_with_nul().len();
+
+        let Ok(ptr) = allocator.alloc_str(method_name_len) else {
+            return ptr::null();
+        };
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                method_name.as_ptr(),
+                ptr.as_ptr(),
+                method_name_len,
+            );
+        }
 
-    ptr.as_ptr()
+        ptr.as_ptr()
+    })
 }
 
 unsafe extern "system" fn get_n_params<T: AddInWrapper>(
@@ -229,7 +294,9 @@ unsafe extern "system" fn get_n_params<T: AddInWrapper>(
     num: c_long,
 ) -> c_long {
     let component = unsafe { this.get_component() };
-    component.addin.get_n_params(num as usize) as c_long
+    component.guard("GetNParams", 0, |component| {
+        component.addin.get_n_params(num as usize) as c_long
+    })
 }
 
 unsafe extern "system" fn get_param_def_value<T: AddInWrapper>(
@@ -239,20 +306,22 @@ unsafe extern "system" fn get_param_def_value<T: AddInWrapper>(
     val: &mut TVariant,
 ) -> bool {
     let component = unsafe { this.get_component() };
-    let Some(mem) = component.memory_manager_ptr else {
-        return false;
-    };
-
-    let def_value_result = component
-        .addin
-        .get_param_def_value(method_num as usize, param_num as usize);
-    match def_value_result {
-        Some(def_value) => {
-            val.update_from_return(mem, &def_value);
-            true
+    component.guard("GetParamDefValue", false, |component| {
+        let Some(mem) = component.memory_manager_ptr else {
+            return false;
+        };
+
+        let def_value_result = component
+            .addin
+            .get_param_def_value(method_num as usize, param_num as usize);
+        match def_value_result {
+            Some(def_value) => component
+                .api_version()
+                .write_value(component.addin.pstr_encoding(), val, mem, &def_value)
+                .is_ok(),
+            None => false,
         }
-        None => false,
-    }
+    })
 }
 
 unsafe extern "system" fn has_ret_val<T: AddInWrapper>(
@@ -260,7 +329,9 @@ unsafe extern "system" fn has_ret_val<T: AddInWrapper>(
     method_num: c_long,
 ) -> bool {
     let component = unsafe { this.get_component() };
-    component.addin.has_ret_val(method_num as usize)
+    component.guard("HasRetVal", false, |component| {
+        component.addin.has_ret_val(method_num as usize)
+    })
 }
 
 unsafe extern "system" fn call_as_proc<T: AddInWrapper>(
@@ -270,32 +341,43 @@ unsafe extern "system" fn call_as_proc<T: AddInWrapper>(
     size_array: c_long,
 ) -> bool {
     let component = unsafe { this.get_component() };
-    let Some(mem_mngr) = component.memory_manager_ptr else {
-        return false;
-    };
-
-    let parameters_raw = if params.is_null() && size_array == 0 { 
-        &mut [] 
-    } else {
-        unsafe { from_raw_parts_mut(params, size_array as usize) }
-    };
-    
-    let mut parameters_values =
-        ParamValues::new(parameters_raw.iter().map(ParamValue::from).collect());
-
-    let call_result = component
-        .addin
-        .call_as_proc(method_num as usize, &mut parameters_values);
-
-    if call_result.is_err() {
-        return false;
-    }
+    component.guard("CallAsProc", false, |component| {
+        let Some(mem_mngr) = component.memory_manager_ptr else {
+            return false;
+        };
+
+        let parameters_raw = if params.is_null() && size_array == 0 {
+            &mut []
+        } else {
+            unsafe { from_raw_parts_mut(params, size_array as usize) }
+        };
+
+        let encoding = component.addin.pstr_encoding();
+        let mut parameters_values = ParamValuesRef::new(
+            parameters_raw
+                .iter()
+                .map(|param| encoding.read_value(param))
+                .collect(),
+        );
 
-    for (i, param) in parameters_values.iter().enumerate() {
-        parameters_raw[i].update_from_return(mem_mngr, param);
-    }
+        let api_version = component.api_version();
+        let call_result = component
+            .addin
+            .call_as_proc_ref(method_num as usize, &mut parameters_values);
+        let modified = parameters_values.into_modified();
+        let call_result = call_result.and_then(|()| {
+            update_params(api_version, encoding, mem_mngr, parameters_raw, modified)
+        });
+
+        if let Err(err) = call_result {
+            let method_name =
+                component.addin.get_method_name(method_num as usize, 0);
+            report_error(component, method_name, "CallAsProc", &err);
+            return false;
+        }
 
-    true
+        true
+    })
 }
 
 unsafe extern "system" fn call_as_func<T: AddInWrapper>(
@@ -306,34 +388,71 @@ unsafe extern "system" fn call_as_func<T: AddInWrapper>(
     size_array: c_long,
 ) -> bool {
     let component = unsafe { this.get_component() };
-    let Some(mem_mngr) = component.memory_manager_ptr else {
-        return false;
-    };
-
-    let parameters_raw = if params.is_null() && size_array == 0 { 
-        &mut [] 
-    } else {
-        unsafe { from_raw_parts_mut(params, size_array as usize) }
-    };
-
-    let mut parameters_values =
-        ParamValues::new(parameters_raw.iter().map(ParamValue::from).collect());
-
-    let call_result = component
-        .addin
-        .call_as_func(method_num as usize, &mut parameters_values);
+    component.guard("CallAsFunc", false, |component| {
+        let Some(mem_mngr) = component.memory_manager_ptr else {
+            return false;
+        };
+
+        let parameters_raw = if params.is_null() && size_array == 0 {
+            &mut []
+        } else {
+            unsafe { from_raw_parts_mut(params, size_array as usize) }
+        };
+
+        let encoding = component.addin.pstr_encoding();
+        let mut parameters_values = ParamValuesRef::new(
+            parameters_raw
+                .iter()
+                .map(|param| encoding.read_value(param))
+                .collect(),
+        );
 
-    let Ok(ret_val) = call_result else {
-        return false;
-    };
+        let api_version = component.api_version();
+        let call_result = component
+            .addin
+            .call_as_func_ref(method_num as usize, &mut parameters_values);
+        let modified = parameters_values.into_modified();
+        // return value is written last, so that it is not left allocated,
+        // when writing of parameters fails and 1C platform does not take it
+        let call_result = call_result.and_then(|ret_val| {
+            update_params(api_version, encoding, mem_mngr, parameters_raw, modified)?;
+            api_version
+                .write_platform_value(encoding, ret_value, mem_mngr, ret_val)?;
+            Ok(())
+        });
+
+        if let Err(err) = call_result {
+            let method_name =
+                component.addin.get_method_name(method_num as usize, 0);
+            report_error(component, method_name, "CallAsFunc", &err);
+            return false;
+        }
 
-    ret_value.update_from_return(mem_mngr, &ret_val);
+        true
+    })
+}
 
-    for (i, param) in parameters_values.iter().enumerate() {
-        parameters_raw[i].update_from_return(mem_mngr, param);
+/// Writes modified parameters back to 1C platform, so that out parameters get
+/// values, set by the AddIn. Unmodified parameters are left as passed by the
+/// platform. Modified values are taken from [`ParamValuesRef`] first, so
+/// that borrowed platform memory is released before it is overwritten.
+/// Fails, if memory for a string or blob value could not be allocated
+fn update_params(
+    api_version: ApiVersion,
+    encoding: PStrEncoding,
+    mem_mngr: &MemoryManager,
+    parameters_raw: &mut [TVariant],
+    modified: Vec<(usize, ParamValue)>,
+) -> Result<(), AddInError> {
+    for (i, value) in modified {
+        api_version.write_value(
+            encoding,
+            &mut parameters_raw[i],
+            mem_mngr,
+            &value,
+        )?;
     }
-
-    true
+    Ok(())
 }
 
 impl<T: AddInWrapper> Default for LanguageExtenderBaseVTable<T> {
@@ -29,5 +29,7 @@ unsafe extern "system" fn set_locale<T: AddInWrapper>(
 ) {
     let component = unsafe { this.get_component() };
     let loc = unsafe { U16CStr::from_ptr_str(loc) };
-    component.addin.set_locale(loc)
+    component.guard("SetLocale", (), |component| {
+        component.addin.set_locale(loc)
+    })
 }
@@ -1,5 +1,7 @@
 use std::{
+    error::Error,
     ffi::{c_ulong, c_void},
+    fmt,
     ptr::{self, NonNull},
 };
 
@@ -25,8 +27,18 @@ pub struct MemoryManager {
     vptr: &'static MemoryManagerVTable,
 }
 
+/// Error of allocating memory with `MemoryManager`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct AllocationError;
 
+impl fmt::Display for AllocationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to allocate memory with MemoryManager")
+    }
+}
+
+impl Error for AllocationError {}
+
 impl MemoryManager {
     /// Safe wrapper around `alloc_memory` method of the MemoryManager object
     /// to allocate memory for byte array
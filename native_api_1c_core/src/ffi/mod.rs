@@ -3,19 +3,24 @@
 //! submodules according to what C++ class they originate from
 //!
 use std::{
-    ffi::{c_long, c_void},
+    ffi::{c_int, c_long, c_void},
     ptr,
+    sync::atomic::{AtomicI32, Ordering},
 };
 
 use crate::interface::AddInWrapper;
 
 use self::{
-    connection::Connection, init_done::InitDoneBaseVTable,
+    api_version::ApiVersion, connection::Connection, init_done::InitDoneBaseVTable,
     lang_extender::LanguageExtenderBaseVTable, locale_base::LocaleBaseVTable,
     memory_manager::MemoryManager,
     user_lang_base::UserLanguageBaseVTable,
 };
 
+/// Differences between Native API 1.0 and 2.0
+pub mod api_version;
+/// Implementation of `AttachedInfo` - replacement for `IAttachedInfo`
+pub mod attached_info;
 /// Implementation of `Connection` - replacement for `IAddInDefBase`
 pub mod connection;
 /// Implementation of `InitDone` - replacement for `IInitDoneBase`
@@ -26,6 +31,14 @@ pub mod lang_extender;
 pub mod locale_base;
 /// Implementation of `MemoryManager` - replacement for `IMemoryManager`
 pub mod memory_manager;
+/// Implementation of `MsgBox` - replacement for `IMsgBox`
+pub mod msg_box;
+/// Catching panics at the FFI boundary and reporting them to 1C platform
+pub mod panic_guard;
+/// Buffers, written directly into memory of 1C platform
+pub mod platform_buf;
+/// Implementation of `PlatformInfo` - replacement for `IPlatformInfo`
+pub mod platform_info;
 /// Implementations of types, provided by Native API for easy of use in Rust
 pub mod provided_types;
 /// Implementation of `UserLanguageBase`
@@ -33,8 +46,7 @@ pub mod user_lang_base;
 
 /// Scheme of attaching to 1C platform process
 #[repr(C)]
-#[derive(Debug)]
-#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AttachType {
     /// Attach to 1C platform process
     NotIsolated = 1,
@@ -44,6 +56,66 @@ pub enum AttachType {
     Any,
 }
 
+/// Level of Native API capabilities, supported by 1C platform or the AddIn.
+/// Negotiated with `SetPlatformCapabilities`, exported by `extern_functions!`
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AppCapabilities {
+    /// `SetPlatformCapabilities` was not called or value is invalid
+    Invalid = -1,
+    V1 = 1,
+    V2 = 2,
+    V3 = 3,
+}
+
+impl AppCapabilities {
+    /// Latest capabilities level, known to this crate
+    pub const LATEST: Self = Self::V3;
+}
+
+impl From<c_int> for AppCapabilities {
+    /// Levels above [`AppCapabilities::LATEST`], passed by newer platforms,
+    /// are treated as `LATEST`
+    fn from(value: c_int) -> Self {
+        match value {
+            1 => Self::V1,
+            2 => Self::V2,
+            3 => Self::V3,
+            value if value > 3 => Self::LATEST,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+static PLATFORM_CAPABILITIES: AtomicI32 =
+    AtomicI32::new(AppCapabilities::Invalid as i32);
+
+/// Implementation of `SetPlatformCapabilities`, called by 1C platform after
+/// loading the library. Stores negotiated capabilities level - the lowest of
+/// platform and AddIn levels, readable with [`platform_capabilities`]
+/// # Arguments
+/// * `capabilities` - level, supported by 1C platform, kept as `c_int`, so
+///   that values unknown to this crate are not undefined behavior
+/// * `supported` - level, supported by the AddIn
+/// # Returns
+/// `AppCapabilities` - level, supported by the AddIn
+pub fn set_platform_capabilities(
+    capabilities: c_int,
+    supported: AppCapabilities,
+) -> AppCapabilities {
+    let negotiated = AppCapabilities::from(capabilities).min(supported);
+    PLATFORM_CAPABILITIES.store(negotiated as i32, Ordering::Relaxed);
+    supported
+}
+
+/// Capabilities level, negotiated with 1C platform in `SetPlatformCapabilities`
+/// # Returns
+/// `AppCapabilities` - negotiated level, `AppCapabilities::Invalid` if
+/// platform did not call `SetPlatformCapabilities`
+pub fn platform_capabilities() -> AppCapabilities {
+    AppCapabilities::from(PLATFORM_CAPABILITIES.load(Ordering::Relaxed))
+}
+
 /// Struct to extract pointer to `Component` from it's interface components
 /// In some places we need to get pointer to `Component` from it's interface
 /// components, so we need to calculate offset of `Component` in memory
@@ -59,8 +131,8 @@ mod offset {
     pub const USER_LANG: usize = 3;
 }
 
-impl<'a, const OFFSET: usize, T: AddInWrapper> This<OFFSET, T> {
-    unsafe fn get_component(&mut self) -> &'a mut Component<T> {
+impl<const OFFSET: usize, T: AddInWrapper> This<OFFSET, T> {
+    unsafe fn get_component(&mut self) -> &mut Component<T> {
         let new_ptr = unsafe {
             (self as *mut This<OFFSET, T> as *mut c_void)
                 .sub(OFFSET * std::mem::size_of::<usize>())
@@ -89,6 +161,25 @@ struct Component<T: AddInWrapper> {
     addin: T,
 }
 
+impl<T: AddInWrapper> Component<T> {
+    /// Native API version, reported by the AddIn with `GetInfo`
+    fn api_version(&self) -> ApiVersion {
+        ApiVersion::from_info(self.addin.get_info())
+    }
+
+    /// Calls `f` with [`panic_guard::guard`], reporting caught panic through
+    /// component's connection, if it is already set
+    fn guard<R>(
+        &mut self,
+        method: &str,
+        on_panic: R,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let connection = self.connection_ptr;
+        panic_guard::guard(connection, method, on_panic, || f(self))
+    }
+}
+
 unsafe extern "system" fn destroy<T: AddInWrapper>(
     component: *mut *mut Component<T>,
 ) {
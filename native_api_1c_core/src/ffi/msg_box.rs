@@ -0,0 +1,75 @@
+use widestring::U16CString;
+
+use super::provided_types::TVariant;
+use crate::interface::ParamValue;
+
+/// VTable for MsgBox object, derived from Native API interface. See original
+/// C++ implementation in [example project](https://its.1c.ru/db/files/1CITS/EXE/VNCOMPS/VNCOMPS.zip)
+/// from 1C documentation. `IInterface` base class has no virtual destructor,
+/// so there are no `dtor` entries
+#[repr(C)]
+struct MsgBoxVTable {
+    confirm:
+        unsafe extern "system" fn(&MsgBox, *const u16, &mut TVariant) -> bool,
+    alert: unsafe extern "system" fn(&MsgBox, *const u16) -> bool,
+}
+
+/// Answer of the user to the question, asked with `MsgBox::confirm`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfirmResult {
+    /// User agreed ("OK" / "Yes")
+    Yes,
+    /// User declined ("Cancel" / "No") or closed the dialog
+    No,
+}
+
+impl ConfirmResult {
+    pub fn is_yes(self) -> bool {
+        self == Self::Yes
+    }
+}
+
+/// MsgBox object, used to show modal dialogs to the user of 1C client.
+/// Obtained with `Connection::msg_box`
+#[repr(C)]
+pub struct MsgBox {
+    vptr: &'static MsgBoxVTable,
+}
+
+impl MsgBox {
+    /// Equivalent to `Confirm` from Native API interface and is used to ask
+    /// the user a yes/no question
+    /// # Arguments
+    /// * `text` - question to ask
+    /// # Returns
+    /// `Option<ConfirmResult>` - answer of the user, or `None` if the dialog
+    /// could not be shown (e.g. on server or in background job)
+    pub fn confirm(&self, text: &str) -> Option<ConfirmResult> {
+        let text_wstr = U16CString::from_str_truncate(text);
+        let mut ret_val = TVariant::default();
+
+        let shown = unsafe {
+            (self.vptr.confirm)(self, text_wstr.as_ptr(), &mut ret_val)
+        };
+        if !shown {
+            return None;
+        }
+
+        match ParamValue::from(&ret_val) {
+            ParamValue::Bool(true) => Some(ConfirmResult::Yes),
+            ParamValue::I32(answer) if answer != 0 => Some(ConfirmResult::Yes),
+            _ => Some(ConfirmResult::No),
+        }
+    }
+
+    /// Equivalent to `Alert` from Native API interface and is used to show
+    /// a message to the user and wait until it is closed
+    /// # Arguments
+    /// * `text` - message to show
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn alert(&self, text: &str) -> bool {
+        let text_wstr = U16CString::from_str_truncate(text);
+        unsafe { (self.vptr.alert)(self, text_wstr.as_ptr()) }
+    }
+}
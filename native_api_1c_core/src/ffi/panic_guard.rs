@@ -0,0 +1,124 @@
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    sync::{Once, PoisonError, RwLock},
+};
+
+use super::connection::Connection;
+use crate::interface::AddInError;
+
+/// Information about a panic, caught at the FFI boundary. Passed to the hook,
+/// set with [`set_panic_hook`]
+/// # Fields
+/// * `method` - name of the Native API method, during which the panic occurred
+/// * `message` - panic message, or `"unknown panic"` if payload is not a string
+/// * `payload` - original panic payload
+/// * `backtrace` - backtrace, captured at the panic site
+pub struct PanicReport<'a> {
+    pub method: &'a str,
+    pub message: &'a str,
+    pub payload: &'a (dyn Any + Send),
+    pub backtrace: Option<&'a Backtrace>,
+}
+
+type PanicHook = Box<dyn Fn(&PanicReport) + Send + Sync>;
+
+static PANIC_HOOK: RwLock<Option<PanicHook>> = RwLock::new(None);
+static BACKTRACE_CAPTURE: Once = Once::new();
+
+thread_local! {
+    static LAST_BACKTRACE: RefCell<Option<Backtrace>> =
+        const { RefCell::new(None) };
+}
+
+/// Sets a hook, that is called for every panic caught at the FFI boundary,
+/// before it is reported to 1C platform. Can be used to log panic payload and
+/// backtrace. Replaces previously set hook.
+///
+/// Backtrace can only be captured at the panic site, so on first call this
+/// function also installs process-wide std panic hook, which captures it
+/// and then calls previously installed std hook.
+pub fn set_panic_hook(hook: impl Fn(&PanicReport) + Send + Sync + 'static) {
+    BACKTRACE_CAPTURE.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            LAST_BACKTRACE.with(|backtrace| {
+                *backtrace.borrow_mut() = Some(Backtrace::force_capture())
+            });
+            previous(info);
+        }));
+    });
+
+    *PANIC_HOOK.write().unwrap_or_else(PoisonError::into_inner) =
+        Some(Box::new(hook));
+}
+
+/// Removes hook, set with [`set_panic_hook`]
+pub fn clear_panic_hook() {
+    *PANIC_HOOK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Calls `f`, catching any panic, so it does not unwind across the FFI
+/// boundary into 1C platform, which would crash the whole process.
+/// Caught panic is reported to 1C platform with `Connection::add_error`, if
+/// connection is available, and `on_panic` is returned instead.
+/// # Arguments
+/// * `connection` - connection to report the panic to
+/// * `method` - name of the Native API method, used as error source
+/// * `on_panic` - value returned to 1C platform if `f` panics
+/// * `f` - function to call
+/// # Returns
+/// `R` - result of `f` or `on_panic`
+pub fn guard<R>(
+    connection: Option<&Connection>,
+    method: &str,
+    on_panic: R,
+    f: impl FnOnce() -> R,
+) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            report(connection, method, payload.as_ref());
+            on_panic
+        }
+    }
+}
+
+fn report(
+    connection: Option<&Connection>,
+    method: &str,
+    payload: &(dyn Any + Send),
+) {
+    let message = panic_message(payload);
+    let backtrace = LAST_BACKTRACE.with(|backtrace| backtrace.take());
+
+    // hook itself is user code, so it must not unwind into platform either
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        let hook = PANIC_HOOK.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(hook) = hook.as_ref() {
+            hook(&PanicReport {
+                method,
+                message,
+                payload,
+                backtrace: backtrace.as_ref(),
+            });
+        }
+    }));
+
+    if let Some(connection) = connection {
+        let error = AddInError::new(format!("panic in `{method}`: {message}"));
+        connection.report_error(method, &error);
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&'static str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
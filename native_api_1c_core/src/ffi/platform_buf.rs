@@ -0,0 +1,353 @@
+use std::{
+    fmt, io,
+    mem::ManuallyDrop,
+    ptr::{self, NonNull},
+    slice,
+};
+
+use widestring::U16Str;
+
+use super::memory_manager::{AllocationError, MemoryManager};
+
+/// Minimal capacity of the first allocation, so that small pushes do not
+/// reallocate every time
+const MIN_CAPACITY: usize = 64;
+
+/// Element of a buffer in memory of 1C platform: `u8` for blobs and `u16`
+/// for strings
+trait Unit: Copy {
+    fn alloc(
+        mem: &MemoryManager,
+        len: usize,
+    ) -> Result<NonNull<Self>, AllocationError>;
+}
+
+impl Unit for u8 {
+    fn alloc(
+        mem: &MemoryManager,
+        len: usize,
+    ) -> Result<NonNull<Self>, AllocationError> {
+        mem.alloc_blob(len)
+    }
+}
+
+impl Unit for u16 {
+    fn alloc(
+        mem: &MemoryManager,
+        len: usize,
+    ) -> Result<NonNull<Self>, AllocationError> {
+        mem.alloc_str(len)
+    }
+}
+
+/// Growable buffer, allocated with `MemoryManager`. `MemoryManager` has no
+/// reallocation, so growing allocates a new block and frees the old one,
+/// capacity is doubled to keep it amortized
+struct RawBuf<T: Unit> {
+    mem: &'static MemoryManager,
+    ptr: Option<NonNull<T>>,
+    len: usize,
+    capacity: usize,
+}
+
+impl<T: Unit> RawBuf<T> {
+    fn new(mem: &'static MemoryManager) -> Self {
+        Self {
+            mem,
+            ptr: None,
+            len: 0,
+            capacity: 0,
+        }
+    }
+
+    /// Makes room for `additional` more elements
+    fn reserve(&mut self, additional: usize) -> Result<(), AllocationError> {
+        let required =
+            self.len.checked_add(additional).ok_or(AllocationError)?;
+        if required <= self.capacity {
+            return Ok(());
+        }
+
+        let capacity = required
+            .max(self.capacity.saturating_mul(2))
+            .max(MIN_CAPACITY)
+            .min(u32::MAX as usize)
+            .max(required);
+        self.reserve_exact(capacity)
+    }
+
+    /// Reallocates the buffer with exactly `capacity` elements
+    fn reserve_exact(
+        &mut self,
+        capacity: usize,
+    ) -> Result<(), AllocationError> {
+        // 1C platform keeps lengths as `u32`
+        if capacity > u32::MAX as usize {
+            return Err(AllocationError);
+        }
+        let new_ptr = T::alloc(self.mem, capacity)?;
+        if let Some(old_ptr) = self.ptr {
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    old_ptr.as_ptr(),
+                    new_ptr.as_ptr(),
+                    self.len,
+                );
+            }
+            self.mem.free_memory(&mut old_ptr.as_ptr().cast());
+        }
+        self.ptr = Some(new_ptr);
+        self.capacity = capacity;
+        Ok(())
+    }
+
+    fn extend_from_slice(&mut self, data: &[T]) -> Result<(), AllocationError> {
+        self.reserve(data.len())?;
+        if let Some(ptr) = self.ptr {
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    data.as_ptr(),
+                    ptr.as_ptr().add(self.len),
+                    data.len(),
+                );
+            }
+        }
+        self.len += data.len();
+        Ok(())
+    }
+
+    fn as_slice(&self) -> &[T] {
+        match self.ptr {
+            Some(ptr) => unsafe {
+                slice::from_raw_parts(ptr.as_ptr(), self.len)
+            },
+            None => &[],
+        }
+    }
+
+    /// Gives up the ownership of the memory, allocating it if nothing was
+    /// written yet
+    fn into_raw(self) -> Result<(NonNull<T>, usize), AllocationError> {
+        let mut this = ManuallyDrop::new(self);
+        if this.ptr.is_none() {
+            let alloc_result = this.reserve_exact(1);
+            if let Err(err) = alloc_result {
+                ManuallyDrop::into_inner(this);
+                return Err(err);
+            }
+        }
+        Ok((this.ptr.unwrap(), this.len))
+    }
+}
+
+impl<T: Unit> Drop for RawBuf<T> {
+    fn drop(&mut self) {
+        if let Some(ptr) = self.ptr.take() {
+            self.mem.free_memory(&mut ptr.as_ptr().cast());
+        }
+    }
+}
+
+/// Blob, written directly into memory of 1C platform. When it is returned to
+/// 1C platform, memory is passed as is, without copying. Implements
+/// [`io::Write`], so files and reports can be written into it
+/// ```ignore
+/// let mut buf = PlatformBuf::with_capacity(mem, file_len)?;
+/// io::copy(&mut file, &mut buf)?;
+/// Ok(buf)
+/// ```
+pub struct PlatformBuf(RawBuf<u8>);
+
+impl PlatformBuf {
+    /// Creates an empty buffer, memory is allocated on the first write
+    pub fn new(mem: &'static MemoryManager) -> Self {
+        Self(RawBuf::new(mem))
+    }
+
+    /// Creates a buffer with memory for `capacity` bytes, so that writing
+    /// of known size allocates only once
+    pub fn with_capacity(
+        mem: &'static MemoryManager,
+        capacity: usize,
+    ) -> Result<Self, AllocationError> {
+        let mut buf = Self::new(mem);
+        buf.reserve(capacity)?;
+        Ok(buf)
+    }
+
+    /// Makes room for `additional` more bytes
+    pub fn reserve(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), AllocationError> {
+        let required =
+            self.0.len.checked_add(additional).ok_or(AllocationError)?;
+        if required > self.0.capacity {
+            self.0.reserve_exact(required)?;
+        }
+        Ok(())
+    }
+
+    /// Appends `data` to the buffer
+    pub fn extend_from_slice(
+        &mut self,
+        data: &[u8],
+    ) -> Result<(), AllocationError> {
+        self.0.extend_from_slice(data)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.len == 0
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    /// Gives up the ownership of the memory, so that it can be passed to
+    /// 1C platform, e.g. with [`super::provided_types::TVariant::update_to_platform_blob`]
+    /// # Returns
+    /// `Result<(NonNull<u8>, usize), AllocationError>` - pointer to the data
+    /// and its length
+    pub fn into_raw(self) -> Result<(NonNull<u8>, usize), AllocationError> {
+        self.0.into_raw()
+    }
+}
+
+impl io::Write for PlatformBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(buf)
+            .map_err(|err| io::Error::new(io::ErrorKind::OutOfMemory, err))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl fmt::Debug for PlatformBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PlatformBuf")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+/// UTF-16 string, written directly into memory of 1C platform. When it is
+/// returned to 1C platform, memory is passed as is, without copying.
+/// Implements [`fmt::Write`], so `write!` can be used to build reports
+/// ```ignore
+/// let mut report = PlatformStr::new(mem);
+/// for row in rows {
+///     writeln!(report, "{}\t{}", row.name, row.total)?;
+/// }
+/// Ok(report)
+/// ```
+pub struct PlatformStr(RawBuf<u16>);
+
+impl PlatformStr {
+    /// Creates an empty string, memory is allocated on the first push
+    pub fn new(mem: &'static MemoryManager) -> Self {
+        Self(RawBuf::new(mem))
+    }
+
+    /// Creates a string with memory for `capacity` UTF-16 code units, so that
+    /// writing of known size allocates only once
+    pub fn with_capacity(
+        mem: &'static MemoryManager,
+        capacity: usize,
+    ) -> Result<Self, AllocationError> {
+        let mut buf = Self::new(mem);
+        buf.reserve(capacity)?;
+        Ok(buf)
+    }
+
+    /// Makes room for `additional` more UTF-16 code units
+    pub fn reserve(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), AllocationError> {
+        // NUL-terminator is written after the data, see `into_raw`
+        let required = self
+            .0
+            .len
+            .checked_add(additional)
+            .and_then(|len| len.checked_add(1))
+            .ok_or(AllocationError)?;
+        if required > self.0.capacity {
+            self.0.reserve_exact(required)?;
+        }
+        Ok(())
+    }
+
+    /// Appends `s`, encoded as UTF-16
+    pub fn push_str(&mut self, s: &str) -> Result<(), AllocationError> {
+        // UTF-16 has at most as many code units as UTF-8 has bytes
+        self.0.reserve(s.len() + 1)?;
+        let mut units = [0u16; 2];
+        for c in s.chars() {
+            self.0.extend_from_slice(c.encode_utf16(&mut units))?;
+        }
+        Ok(())
+    }
+
+    /// Appends one character
+    pub fn push(&mut self, c: char) -> Result<(), AllocationError> {
+        let mut units = [0u16; 2];
+        self.push_utf16(c.encode_utf16(&mut units))
+    }
+
+    /// Appends UTF-16 code units as is
+    pub fn push_utf16(&mut self, s: &[u16]) -> Result<(), AllocationError> {
+        // keeps room for NUL-terminator, so that `into_raw` does not reallocate
+        self.0.reserve(s.len() + 1)?;
+        self.0.extend_from_slice(s)
+    }
+
+    /// Length in UTF-16 code units
+    pub fn len(&self) -> usize {
+        self.0.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.len == 0
+    }
+
+    pub fn as_ustr(&self) -> &U16Str {
+        U16Str::from_slice(self.0.as_slice())
+    }
+
+    /// Gives up the ownership of the memory, so that it can be passed to
+    /// 1C platform, e.g. with [`super::provided_types::TVariant::update_to_platform_str`].
+    /// NUL-terminator is written after the data, but not counted in length
+    /// # Returns
+    /// `Result<(NonNull<u16>, usize), AllocationError>` - pointer to the
+    /// data and its length
+    pub fn into_raw(
+        mut self,
+    ) -> Result<(NonNull<u16>, usize), AllocationError> {
+        self.0.reserve(1)?;
+        let (ptr, len) = self.0.into_raw()?;
+        unsafe { ptr.as_ptr().add(len).write(0) };
+        Ok((ptr, len))
+    }
+}
+
+impl fmt::Write for PlatformStr {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl fmt::Debug for PlatformStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PlatformStr")
+            .field(&self.as_ustr().to_string_lossy())
+            .finish()
+    }
+}
@@ -0,0 +1,111 @@
+use std::ffi::c_int;
+
+use widestring::U16CStr;
+
+/// Type of 1C application, the AddIn is loaded into.
+/// Equivalent to `IPlatformInfo::AppType`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppType {
+    Unknown = -1,
+    ThinClient = 0,
+    ThickClient,
+    WebClient,
+    Server,
+    /// External connection (COM connector)
+    ExtConn,
+    MobileClient,
+    MobileServer,
+}
+
+impl AppType {
+    /// `true` for applications, that interact with the user
+    pub fn is_client(self) -> bool {
+        matches!(
+            self,
+            Self::ThinClient
+                | Self::ThickClient
+                | Self::WebClient
+                | Self::MobileClient
+        )
+    }
+
+    /// `true` for applications, that run on 1C server
+    pub fn is_server(self) -> bool {
+        matches!(self, Self::Server | Self::MobileServer)
+    }
+}
+
+impl From<c_int> for AppType {
+    fn from(value: c_int) -> Self {
+        match value {
+            0 => Self::ThinClient,
+            1 => Self::ThickClient,
+            2 => Self::WebClient,
+            3 => Self::Server,
+            4 => Self::ExtConn,
+            5 => Self::MobileClient,
+            6 => Self::MobileServer,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Layout of `IPlatformInfo::AppInfo`. `Application` is kept as `c_int`, so
+/// that values unknown to this crate are not undefined behavior
+#[repr(C)]
+struct AppInfoRaw {
+    app_version: *const u16,
+    user_agent_information: *const u16,
+    application: c_int,
+}
+
+/// Information about 1C platform, obtained with `Connection::platform_info`
+/// # Fields
+/// * `app_version` - version of 1C platform, e.g. `"8.3.24.1548"`
+/// * `application` - type of 1C application
+/// * `user_agent` - user agent of the browser for web client, empty otherwise
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppInfo {
+    pub app_version: String,
+    pub application: AppType,
+    pub user_agent: String,
+}
+
+/// VTable for PlatformInfo object, derived from Native API interface. See original
+/// C++ implementation in [example project](https://its.1c.ru/db/files/1CITS/EXE/VNCOMPS/VNCOMPS.zip)
+/// from 1C documentation
+#[repr(C)]
+struct PlatformInfoVTable {
+    get_platform_info:
+        unsafe extern "system" fn(&PlatformInfo) -> *const AppInfoRaw,
+}
+
+/// PlatformInfo object, used to get information about 1C platform.
+/// Obtained with `Connection::get_interface`
+#[repr(C)]
+pub struct PlatformInfo {
+    vptr: &'static PlatformInfoVTable,
+}
+
+impl PlatformInfo {
+    /// Equivalent to `GetPlatformInfo` from Native API interface
+    /// # Returns
+    /// `Option<AppInfo>` - information about 1C platform, `None` if it is
+    /// not provided
+    pub fn get_platform_info(&self) -> Option<AppInfo> {
+        let raw = unsafe { (self.vptr.get_platform_info)(self).as_ref() }?;
+
+        Some(AppInfo {
+            app_version: unsafe { wstr_to_string(raw.app_version) },
+            application: AppType::from(raw.application),
+            user_agent: unsafe { wstr_to_string(raw.user_agent_information) },
+        })
+    }
+}
+
+unsafe fn wstr_to_string(ptr: *const u16) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    unsafe { U16CStr::from_ptr_str(ptr) }.to_string_lossy()
+}
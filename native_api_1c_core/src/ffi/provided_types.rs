@@ -1,15 +1,23 @@
 use std::{
-    ffi::{c_int, c_void},
-    ptr,
+    borrow::Cow,
+    ffi::{c_char, c_int, c_long, c_void},
+    fmt, ptr,
     slice::from_raw_parts,
 };
-use widestring::U16CString;
+use widestring::U16Str;
 
-use chrono::{Datelike, Timelike};
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, LocalResult, NaiveDateTime,
+    TimeZone, Timelike, Utc,
+};
 
-use crate::interface::ParamValue;
+use crate::interface::{ParamValue, ParamValueRef, PlatformValue};
 
-use super::memory_manager::{AllocationError, MemoryManager};
+use super::{
+    api_version::PStrEncoding,
+    memory_manager::{AllocationError, MemoryManager},
+    platform_buf::{PlatformBuf, PlatformStr},
+};
 
 /// Type representing 1C date and time values
 /// # Fields
@@ -21,11 +29,13 @@ use super::memory_manager::{AllocationError, MemoryManager};
 /// * `year` - years since 1900
 /// * `wday` - days since Sunday - [0, 6]
 /// * `yday` - days since January 1 - [0, 365]
-/// * `isdst` - daylight savings time flag
+/// * `isdst` - daylight savings time flag, positive if DST is in effect,
+///   zero if not, negative if unknown
 /// * `gmtoff` - seconds east of UTC (unix only)
-/// * `zone` - timezone abbreviation (unix only)
+/// * `zone` - timezone abbreviation (unix only), C string owned by 1C
+///   platform or C runtime, it is never dereferenced by this crate
 #[repr(C)]
-#[derive(Clone, Copy, Default, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct Tm {
     pub sec: c_int,
     pub min: c_int,
@@ -40,42 +50,159 @@ pub struct Tm {
     #[cfg(target_family = "unix")]
     pub gmtoff: std::ffi::c_long,
     #[cfg(target_family = "unix")]
-    pub zone: std::ffi::c_char,
+    pub zone: *const c_char,
 }
 
-impl From<&Tm> for chrono::NaiveDateTime {
-    fn from(value: &Tm) -> Self {
-        let default_time = chrono::NaiveDate::from_ymd_opt(1970, 1, 1)
-            .unwrap()
-            .and_hms_opt(0, 0, 0)
-            .unwrap();
-
-        let from_result = chrono::NaiveDate::from_ymd_opt(
-            1900 + value.year,
-            (1 + value.mon) as u32,
-            (value.mday) as u32,
-        )
-        .ok_or(());
-        if from_result.is_err() {
-            return default_time;
+impl Default for Tm {
+    fn default() -> Self {
+        Self {
+            sec: 0,
+            min: 0,
+            hour: 0,
+            mday: 0,
+            mon: 0,
+            year: 0,
+            wday: 0,
+            yday: 0,
+            isdst: 0,
+            #[cfg(target_family = "unix")]
+            gmtoff: 0,
+            #[cfg(target_family = "unix")]
+            zone: ptr::null(),
         }
+    }
+}
+
+// SAFETY: `zone` is only copied, never dereferenced
+unsafe impl Send for Tm {}
+unsafe impl Sync for Tm {}
+
+impl Tm {
+    /// 1C empty date - `0001-01-01 00:00:00` (`'00010101'` in 1C)
+    pub const EMPTY_DATE: Tm = Tm {
+        sec: 0,
+        min: 0,
+        hour: 0,
+        mday: 1,
+        mon: 0,
+        year: 1 - 1900,
+        wday: 1,
+        yday: 0,
+        isdst: 0,
+        #[cfg(target_family = "unix")]
+        gmtoff: 0,
+        #[cfg(target_family = "unix")]
+        zone: ptr::null(),
+    };
+
+    /// First year, representable in 1C
+    pub const MIN_YEAR: c_int = 1;
+    /// Last year, representable in 1C
+    pub const MAX_YEAR: c_int = 9999;
+
+    /// Checks if value is 1C empty date, ignoring derived fields
+    /// (`wday`, `yday`, `isdst`)
+    pub fn is_empty_date(&self) -> bool {
+        self.year == Self::EMPTY_DATE.year
+            && self.mon == Self::EMPTY_DATE.mon
+            && self.mday == Self::EMPTY_DATE.mday
+            && self.hour == 0
+            && self.min == 0
+            && self.sec == 0
+    }
 
-        let from_result = from_result.unwrap().and_hms_opt(
-            value.hour as u32,
-            value.min as u32,
-            value.sec as u32,
-        );
-        if from_result.is_none() {
-            return default_time;
+    /// Converts value to date-time, treating 1C empty date as `None`
+    pub fn to_optional_date_time(
+        &self,
+    ) -> Result<Option<chrono::NaiveDateTime>, DateRangeError> {
+        if self.is_empty_date() {
+            return Ok(None);
         }
+        chrono::NaiveDateTime::try_from(self).map(Some)
+    }
 
-        from_result.unwrap()
+    /// Converts optional date-time to value, `None` becomes 1C empty date
+    pub fn from_optional_date_time(
+        value: Option<chrono::NaiveDateTime>,
+    ) -> Self {
+        value.map_or(Self::EMPTY_DATE, Self::from)
     }
 }
 
-impl From<Tm> for chrono::NaiveDateTime {
-    fn from(value: Tm) -> Self {
-        Self::from(&value)
+/// Error of converting `Tm` to date-time: date does not exist (e.g. February
+/// 30th) or is outside of 1C date range (years 1 to 9999)
+/// # Fields
+/// * `year`, `month`, `day`, `hour`, `min`, `sec` - calendar values of `Tm`,
+///   `year` and `month` are not offset as in `Tm`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateRangeError {
+    pub year: i64,
+    pub month: i64,
+    pub day: c_int,
+    pub hour: c_int,
+    pub min: c_int,
+    pub sec: c_int,
+}
+
+impl From<&Tm> for DateRangeError {
+    fn from(value: &Tm) -> Self {
+        Self {
+            year: i64::from(value.year) + 1900,
+            month: i64::from(value.mon) + 1,
+            day: value.mday,
+            hour: value.hour,
+            min: value.min,
+            sec: value.sec,
+        }
+    }
+}
+
+impl fmt::Display for DateRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "date {:04}-{:02}-{:02} {:02}:{:02}:{:02} is invalid or out of range",
+            self.year, self.month, self.day, self.hour, self.min, self.sec
+        )
+    }
+}
+
+impl std::error::Error for DateRangeError {}
+
+impl TryFrom<&Tm> for chrono::NaiveDateTime {
+    type Error = DateRangeError;
+
+    /// 1C empty date becomes `0001-01-01 00:00:00`, use
+    /// `Tm::to_optional_date_time` to get `None` for it instead
+    fn try_from(value: &Tm) -> Result<Self, Self::Error> {
+        let error = || DateRangeError::from(value);
+
+        let year = value.year.checked_add(1900).ok_or_else(error)?;
+        if !(Tm::MIN_YEAR..=Tm::MAX_YEAR).contains(&year) {
+            return Err(error());
+        }
+        let month = value
+            .mon
+            .checked_add(1)
+            .and_then(|month| u32::try_from(month).ok())
+            .ok_or_else(error)?;
+        let day = u32::try_from(value.mday).map_err(|_| error())?;
+        let hour = u32::try_from(value.hour).map_err(|_| error())?;
+        let min = u32::try_from(value.min).map_err(|_| error())?;
+        // leap second is not representable in 1C, it is clamped
+        let sec = u32::try_from(value.sec.min(59)).map_err(|_| error())?;
+
+        chrono::NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, min, sec))
+            .ok_or_else(error)
+    }
+}
+
+impl TryFrom<Tm> for chrono::NaiveDateTime {
+    type Error = DateRangeError;
+
+    fn try_from(value: Tm) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
     }
 }
 
@@ -94,7 +221,7 @@ impl From<&chrono::NaiveDateTime> for Tm {
             #[cfg(target_family = "unix")]
             gmtoff: 0,
             #[cfg(target_family = "unix")]
-            zone: 0,
+            zone: ptr::null(),
         }
     }
 }
@@ -105,6 +232,128 @@ impl From<chrono::NaiveDateTime> for Tm {
     }
 }
 
+/// 1C dates have no timezone, they are wall-clock time of the process, 1C
+/// platform runs in (server time for server calls, client time otherwise).
+/// Conversions with timezone-aware date-times follow this rule
+impl Tm {
+    /// Offset from UTC, provided by 1C platform in `gmtoff` and `zone`
+    /// (unix only)
+    /// # Returns
+    /// `Option<FixedOffset>` - offset, or `None` if it is not provided, in
+    /// which case time is local time of the process
+    pub fn utc_offset(&self) -> Option<FixedOffset> {
+        #[cfg(target_family = "unix")]
+        if self.gmtoff != 0 || !self.zone.is_null() {
+            return i32::try_from(self.gmtoff)
+                .ok()
+                .and_then(FixedOffset::east_opt);
+        }
+        None
+    }
+
+    /// Converts value to date-time with offset. Offset is taken from
+    /// `gmtoff` if it is provided, otherwise local timezone of the process is
+    /// used, and `isdst` chooses between repeated times, when clocks are
+    /// turned back
+    pub fn to_date_time_tz(
+        &self,
+    ) -> Result<DateTime<FixedOffset>, DateRangeError> {
+        let error = || DateRangeError::from(self);
+        let naive = NaiveDateTime::try_from(self)?;
+
+        if let Some(offset) = self.utc_offset() {
+            return offset
+                .from_local_datetime(&naive)
+                .single()
+                .ok_or_else(error);
+        }
+
+        let local = match Local.from_local_datetime(&naive) {
+            LocalResult::Single(local) => local,
+            LocalResult::Ambiguous(dst, standard) => {
+                if self.isdst > 0 {
+                    dst
+                } else {
+                    standard
+                }
+            }
+            // skipped, when clocks are turned forward
+            LocalResult::None => return Err(error()),
+        };
+        Ok(local.fixed_offset())
+    }
+}
+
+/// `true` if DST is in effect: offset is greater than the smallest offset of
+/// the year, taken in January and July to cover both hemispheres
+fn is_dst(value: &DateTime<Local>) -> bool {
+    let offset = value.offset().local_minus_utc();
+    [1, 7]
+        .into_iter()
+        .filter_map(|month| {
+            Local
+                .with_ymd_and_hms(value.year(), month, 1, 0, 0, 0)
+                .earliest()
+        })
+        .map(|date| date.offset().local_minus_utc())
+        .min()
+        .is_some_and(|standard| offset > standard)
+}
+
+impl TryFrom<&Tm> for DateTime<FixedOffset> {
+    type Error = DateRangeError;
+
+    fn try_from(value: &Tm) -> Result<Self, Self::Error> {
+        value.to_date_time_tz()
+    }
+}
+
+impl TryFrom<Tm> for DateTime<FixedOffset> {
+    type Error = DateRangeError;
+
+    fn try_from(value: Tm) -> Result<Self, Self::Error> {
+        value.to_date_time_tz()
+    }
+}
+
+impl TryFrom<&Tm> for DateTime<Utc> {
+    type Error = DateRangeError;
+
+    fn try_from(value: &Tm) -> Result<Self, Self::Error> {
+        value.to_date_time_tz().map(|date| date.to_utc())
+    }
+}
+
+impl TryFrom<Tm> for DateTime<Utc> {
+    type Error = DateRangeError;
+
+    fn try_from(value: Tm) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+/// Date-time is converted to local time of the process, `gmtoff` and `isdst`
+/// are set accordingly
+impl<Tz: TimeZone> From<&DateTime<Tz>> for Tm {
+    fn from(value: &DateTime<Tz>) -> Self {
+        let local = value.with_timezone(&Local);
+        Self {
+            isdst: c_int::from(is_dst(&local)),
+            #[cfg(target_family = "unix")]
+            gmtoff: c_long::from(local.offset().local_minus_utc()),
+            ..Self::from(local.naive_local())
+        }
+    }
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for Tm {
+    fn from(value: DateTime<Tz>) -> Self {
+        Self::from(&value)
+    }
+}
+
+/// `zone` is not compared: it is a pointer to a name, owned by C runtime,
+/// and equal dates may point to different copies of the same name
 #[cfg(target_family = "unix")]
 impl PartialEq for Tm {
     fn eq(&self, other: &Self) -> bool {
@@ -118,7 +367,6 @@ impl PartialEq for Tm {
             && self.yday == other.yday
             && self.isdst == other.isdst
             && self.gmtoff == other.gmtoff
-            && self.zone == other.zone
     }
 }
 
@@ -194,13 +442,16 @@ impl<'a> ReturnValue<'a> {
 
     /// Sets the value of the ReturnValue object to UTF-16 `&[u16]`
     pub fn set_str(self, val: &[u16]) {
-        let Ok(ptr) = self.mem.alloc_str(val.len()) else {
+        // NUL-terminator is not counted in `len`, but added for hosts,
+        // that read the string as C string
+        let Ok(ptr) = self.mem.alloc_str(val.len() + 1) else {
             *self.result = false;
             return;
         };
 
         unsafe {
-            ptr::copy_nonoverlapping(val.as_ptr(), ptr.as_ptr(), val.len())
+            ptr::copy_nonoverlapping(val.as_ptr(), ptr.as_ptr(), val.len());
+            ptr.as_ptr().add(val.len()).write(0);
         };
 
         self.variant.vt = VariantType::WStr;
@@ -226,33 +477,105 @@ impl<'a> ReturnValue<'a> {
 }
 
 impl<'a> From<&'a TVariant> for ParamValue {
+    /// See `From<&TVariant>` for [`ParamValueRef`]
     fn from(param: &'a TVariant) -> ParamValue {
+        ParamValueRef::from(param).into_owned()
+    }
+}
+
+impl<'a> From<&'a TVariant> for ParamValueRef<'a> {
+    /// Decodes every value type, 1C platform or other hosts can send.
+    /// Integers are widened to `I32`, `I64` or `U64` without loss, `Float` to
+    /// `F64`, OLE `Date` to `Date` (or `F64` if it is out of range),
+    /// `HResult` to `Error`, `PStr` to `String`, decoded as UTF-8 (see
+    /// [`PStrEncoding::read_value`] for other encodings). Only `Interface`
+    /// and `Variant` references, that cannot be used outside of the call,
+    /// become `Empty`. `WStr` and `Blob` data is borrowed, not copied
+    fn from(param: &'a TVariant) -> ParamValueRef<'a> {
         unsafe {
             match param.vt {
                 VariantType::Empty => Self::Empty,
+                VariantType::Null => Self::Null,
                 VariantType::Bool => Self::Bool(param.value.bool),
+                VariantType::Int8 => Self::I32(param.value.i8.into()),
+                VariantType::Int16 => Self::I32(param.value.i16.into()),
                 VariantType::Int32 => Self::I32(param.value.i32),
+                VariantType::Int => Self::I32(param.value.i32),
+                VariantType::Int64 => Self::I64(param.value.i64),
+                VariantType::UInt8 => Self::I32(param.value.u8.into()),
+                VariantType::UInt16 => Self::I32(param.value.u16.into()),
+                VariantType::UInt32 => Self::I64(param.value.u32.into()),
+                VariantType::UInt => Self::I64(param.value.u32.into()),
+                VariantType::UInt64 => Self::U64(param.value.u64),
+                VariantType::Float => Self::F64(param.value.f32.into()),
                 VariantType::Double => Self::F64(param.value.f64),
+                VariantType::Date => match ole_date_to_tm(param.value.date) {
+                    Some(tm) => Self::Date(tm),
+                    None => Self::F64(param.value.date),
+                },
                 VariantType::Time => Self::Date(param.value.tm),
-                VariantType::WStr => Self::String(
-                    U16CString::from_ptr_truncate(
-                        param.value.data_str.ptr,
-                        param.value.data_str.len as usize,
-                    ),
-                ),
-                VariantType::Blob => Self::Blob(
-                    from_raw_parts(
-                        param.value.data_blob.ptr,
-                        param.value.data_blob.len as usize,
-                    )
-                    .into(),
-                ),
-                _ => Self::Empty,
+                VariantType::Error => Self::Error(param.value.error_code),
+                // HRESULT is 32-bit, even where `long` is wider
+                VariantType::HResult => Self::Error(param.value.hresult as i32),
+                VariantType::ClsID => Self::ClsId(param.value.clsid),
+                VariantType::PStr => {
+                    let bytes = param.as_pstr().unwrap_or_default();
+                    Self::String(Cow::Owned(PStrEncoding::Utf8.decode(bytes)))
+                }
+                VariantType::WStr => {
+                    let data_str = param.value.data_str;
+                    if data_str.ptr.is_null() {
+                        Self::String(Cow::Borrowed(U16Str::from_slice(&[])))
+                    } else {
+                        Self::String(Cow::Borrowed(U16Str::from_ptr(
+                            data_str.ptr,
+                            data_str.len as usize,
+                        )))
+                    }
+                }
+                VariantType::Blob => {
+                    let data_blob = param.value.data_blob;
+                    if data_blob.ptr.is_null() {
+                        Self::Blob(&[])
+                    } else {
+                        Self::Blob(from_raw_parts(
+                            data_blob.ptr,
+                            data_blob.len as usize,
+                        ))
+                    }
+                }
+                VariantType::Interface
+                | VariantType::Variant
+                | VariantType::Undefined => Self::Empty,
             }
         }
     }
 }
 
+/// Converts OLE automation date (`DATE`) - days since 1899-12-30, with time
+/// of day as fractional part - to `Tm`, rounding to seconds
+/// # Returns
+/// `Option<Tm>` - `None` if value is not finite or out of range
+pub fn ole_date_to_tm(value: f64) -> Option<Tm> {
+    // far beyond any date, representable in 1C or chrono
+    const MAX_DAYS: f64 = 100_000_000.0;
+
+    if !value.is_finite() || value.abs() > MAX_DAYS {
+        return None;
+    }
+
+    // for negative dates fractional part is still time after midnight
+    let days = value.trunc();
+    let seconds = ((value - days).abs() * 86_400.0).round();
+
+    let date_time = chrono::NaiveDate::from_ymd_opt(1899, 12, 30)?
+        .and_hms_opt(0, 0, 0)?
+        .checked_add_signed(chrono::Duration::days(days as i64))?
+        .checked_add_signed(chrono::Duration::seconds(seconds as i64))?;
+
+    Some(Tm::from(date_time))
+}
+
 #[repr(u16)]
 #[allow(dead_code)]
 #[derive(PartialEq, Debug)]
@@ -308,20 +631,50 @@ pub struct DataBlob {
     pub len: u32,
 }
 
+/// Type representing stored single-byte string data
+/// # Fields
+/// * `ptr` - pointer to the data
+/// * `len` - length of the data in bytes
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct DataPStr {
+    pub ptr: *mut c_char,
+    pub len: u32,
+}
+
 /// Type encapsulating 1C variant values
 /// # Fields
 /// * `bool` - boolean value
-/// * `i32` - integer value
-/// * `f64` - float value
+/// * `i8`, `i16`, `i32`, `i64` - signed integer values
+/// * `u8`, `u16`, `u32`, `u64` - unsigned integer values
+/// * `f32`, `f64` - float values
+/// * `date` - OLE automation date (`DATE`), days since 1899-12-30
+/// * `error_code` - error code value
+/// * `hresult` - `HRESULT` value
 /// * `tm` - date-time value
+/// * `clsid` - UUID value
+/// * `data_pstr` - single-byte string value, see [`PStrEncoding`]
 /// * `data_str` - UTF-16 string value
 /// * `data_blob` - blob value
 #[repr(C)]
 pub union VariantValue {
     pub bool: bool,
+    pub i8: i8,
+    pub i16: i16,
     pub i32: i32,
+    pub i64: i64,
+    pub u8: u8,
+    pub u16: u16,
+    pub u32: u32,
+    pub u64: u64,
+    pub f32: f32,
     pub f64: f64,
+    pub date: f64,
+    pub error_code: i32,
+    pub hresult: c_long,
     pub tm: Tm,
+    pub clsid: [u8; 16],
+    pub data_pstr: DataPStr,
     pub data_str: DataStr,
     pub data_blob: DataBlob,
 }
@@ -337,7 +690,8 @@ pub struct TVariant {
 impl Default for TVariant {
     fn default() -> Self {
         Self {
-            value: VariantValue { bool: false },
+            // `tm` is the largest member, so the whole value is zeroed
+            value: VariantValue { tm: Tm::default() },
             elements: 0,
             vt: VariantType::Empty,
         }
@@ -345,52 +699,171 @@ impl Default for TVariant {
 }
 
 impl TVariant {
+    /// Bytes of `PStr` string, `None` for other types
+    pub fn as_pstr(&self) -> Option<&[u8]> {
+        if self.vt != VariantType::PStr {
+            return None;
+        }
+        let data_pstr = unsafe { self.value.data_pstr };
+        if data_pstr.ptr.is_null() {
+            return Some(&[]);
+        }
+        Some(unsafe {
+            from_raw_parts(data_pstr.ptr.cast::<u8>(), data_pstr.len as usize)
+        })
+    }
+
+    /// Replaces the value with a copy of UTF-16 string `v`, allocated with
+    /// `mem_mngr`. Previous string or blob data is freed only after the
+    /// allocation succeeds, so on error the variant is left unchanged
     /// # Safety
     /// This function is unsafe because it manipulates pointers, provided by the 1C platform.
-    /// Function is safe as long as 1C platform provides valid pointers.
+    /// Function is safe as long as string or blob data was allocated with `mem_mngr`.
     pub unsafe fn update_to_str(
         &mut self,
         mem_mngr: &MemoryManager,
         v: &[u16],
     ) -> Result<u32, AllocationError> {
-        let data_str = unsafe { &mut self.value.data_str };
-        let old_pointer = data_str.ptr;
+        // NUL-terminator is not counted in `len`, see `ReturnValue::set_str`
+        let ptr = mem_mngr.alloc_str(v.len() + 1)?;
+        unsafe {
+            ptr::copy_nonoverlapping(v.as_ptr(), ptr.as_ptr(), v.len());
+            ptr.as_ptr().add(v.len()).write(0);
+            self.free_data(mem_mngr);
+        };
 
-        let ptr = mem_mngr.alloc_str(v.len())?;
-        unsafe { ptr::copy_nonoverlapping(v.as_ptr(), ptr.as_ptr(), v.len()) };
+        self.value.data_str = DataStr {
+            ptr: ptr.as_ptr(),
+            len: v.len() as u32,
+        };
+        self.vt = VariantType::WStr;
 
-        data_str.ptr = ptr.as_ptr();
-        data_str.len = v.len() as u32;
+        Ok(v.len() as u32)
+    }
 
-        mem_mngr.free_memory(&mut old_pointer.cast::<c_void>());
+    /// Replaces the value with a copy of single-byte string `v`, allocated
+    /// with `mem_mngr`, as Native API 1.0 hosts expect, see
+    /// [`super::api_version::ApiVersion`]. Previous string or blob data is
+    /// freed only after the allocation succeeds
+    /// # Safety
+    /// This function is unsafe because it manipulates pointers, provided by the 1C platform.
+    /// Function is safe as long as string or blob data was allocated with `mem_mngr`.
+    pub unsafe fn update_to_pstr(
+        &mut self,
+        mem_mngr: &MemoryManager,
+        v: &[u8],
+    ) -> Result<u32, AllocationError> {
+        // NUL-terminator is not counted in `len`, see `ReturnValue::set_str`
+        let ptr = mem_mngr.alloc_blob(v.len() + 1)?;
+        unsafe {
+            ptr::copy_nonoverlapping(v.as_ptr(), ptr.as_ptr(), v.len());
+            ptr.as_ptr().add(v.len()).write(0);
+            self.free_data(mem_mngr);
+        };
 
-        self.vt = VariantType::WStr;
+        self.value.data_pstr = DataPStr {
+            ptr: ptr.as_ptr().cast(),
+            len: v.len() as u32,
+        };
+        self.vt = VariantType::PStr;
 
-        Ok(data_str.len)
+        Ok(v.len() as u32)
     }
 
+    /// Replaces the value with a copy of blob `v`, allocated with `mem_mngr`.
+    /// Previous string or blob data is freed only after the allocation
+    /// succeeds, so on error the variant is left unchanged
     /// # Safety
     /// This function is unsafe because it manipulates pointers, provided by the 1C platform.
-    /// Function is safe as long as 1C platform provides valid pointers.
+    /// Function is safe as long as string or blob data was allocated with `mem_mngr`.
     pub unsafe fn update_to_blob(
         &mut self,
         mem_mngr: &MemoryManager,
         v: &[u8],
     ) -> Result<u32, AllocationError> {
-        let data_blob = unsafe { &mut self.value.data_blob };
-        let old_pointer = data_blob.ptr;
-
         let ptr = mem_mngr.alloc_blob(v.len())?;
-        unsafe { ptr::copy_nonoverlapping(v.as_ptr(), ptr.as_ptr(), v.len()) };
+        unsafe {
+            ptr::copy_nonoverlapping(v.as_ptr(), ptr.as_ptr(), v.len());
+            self.free_data(mem_mngr);
+        };
 
-        data_blob.ptr = ptr.as_ptr();
-        data_blob.len = v.len() as u32;
+        self.value.data_blob = DataBlob {
+            ptr: ptr.as_ptr(),
+            len: v.len() as u32,
+        };
+        self.vt = VariantType::Blob;
 
-        mem_mngr.free_memory(&mut old_pointer.cast::<c_void>());
+        Ok(v.len() as u32)
+    }
 
+    /// Replaces the value with string `v`, already allocated with `mem_mngr`,
+    /// so it is passed to 1C platform without copying
+    /// # Safety
+    /// This function is unsafe because it manipulates pointers, provided by the 1C platform.
+    /// Function is safe as long as string or blob data was allocated with `mem_mngr`.
+    pub unsafe fn update_to_platform_str(
+        &mut self,
+        mem_mngr: &MemoryManager,
+        v: PlatformStr,
+    ) -> Result<u32, AllocationError> {
+        let (ptr, len) = v.into_raw()?;
+        unsafe { self.free_data(mem_mngr) };
+
+        self.value.data_str = DataStr {
+            ptr: ptr.as_ptr(),
+            len: len as u32,
+        };
+        self.vt = VariantType::WStr;
+
+        Ok(len as u32)
+    }
+
+    /// Replaces the value with blob `v`, already allocated with `mem_mngr`,
+    /// so it is passed to 1C platform without copying
+    /// # Safety
+    /// This function is unsafe because it manipulates pointers, provided by the 1C platform.
+    /// Function is safe as long as string or blob data was allocated with `mem_mngr`.
+    pub unsafe fn update_to_platform_blob(
+        &mut self,
+        mem_mngr: &MemoryManager,
+        v: PlatformBuf,
+    ) -> Result<u32, AllocationError> {
+        let (ptr, len) = v.into_raw()?;
+        unsafe { self.free_data(mem_mngr) };
+
+        self.value.data_blob = DataBlob {
+            ptr: ptr.as_ptr(),
+            len: len as u32,
+        };
         self.vt = VariantType::Blob;
 
-        Ok(data_blob.len)
+        Ok(len as u32)
+    }
+
+    /// Frees string or blob data of the variant with `MemoryManager` and
+    /// sets it to empty. Other types of values are just reset to empty
+    /// # Safety
+    /// This function is unsafe because it manipulates pointers, provided by the 1C platform.
+    /// Function is safe as long as string or blob data was allocated with `mem_mngr`.
+    pub unsafe fn free_data(&mut self, mem_mngr: &MemoryManager) {
+        let mut ptr = self.owned_data();
+        if !ptr.is_null() {
+            mem_mngr.free_memory(&mut ptr);
+        }
+        self.vt = VariantType::Empty;
+    }
+
+    /// Pointer to data, owned by the variant: `PStr`, `WStr` and `Blob`
+    /// values are allocated with `MemoryManager` by 1C platform (parameters)
+    /// or by the AddIn (out parameters and return values). For other types
+    /// the union holds no pointer, so its bits must never be freed
+    fn owned_data(&self) -> *mut c_void {
+        match self.vt {
+            VariantType::PStr => unsafe { self.value.data_pstr.ptr.cast() },
+            VariantType::WStr => unsafe { self.value.data_str.ptr.cast() },
+            VariantType::Blob => unsafe { self.value.data_blob.ptr.cast() },
+            _ => ptr::null_mut(),
+        }
     }
 
     pub fn update_to_bool(&mut self, v: bool) {
@@ -403,6 +876,26 @@ impl TVariant {
         self.vt = VariantType::Int32;
     }
 
+    pub fn update_to_i64(&mut self, v: i64) {
+        self.value.i64 = v;
+        self.vt = VariantType::Int64;
+    }
+
+    pub fn update_to_u64(&mut self, v: u64) {
+        self.value.u64 = v;
+        self.vt = VariantType::UInt64;
+    }
+
+    pub fn update_to_error(&mut self, v: i32) {
+        self.value.error_code = v;
+        self.vt = VariantType::Error;
+    }
+
+    pub fn update_to_clsid(&mut self, v: [u8; 16]) {
+        self.value.clsid = v;
+        self.vt = VariantType::ClsID;
+    }
+
     pub fn update_to_f64(&mut self, v: f64) {
         self.value.f64 = v;
         self.vt = VariantType::Double;
@@ -413,22 +906,54 @@ impl TVariant {
         self.vt = VariantType::Time;
     }
 
+    /// Replaces the value with `value`, freeing previous string or blob data
+    /// # Returns
+    /// `Result<(), AllocationError>` - error, if memory for string or blob
+    /// could not be allocated, the variant is left unchanged then
     pub fn update_from_return(
         &mut self,
         mem_mngr: &MemoryManager,
         value: &ParamValue,
-    ) {
+    ) -> Result<(), AllocationError> {
+        if !matches!(value, ParamValue::String(_) | ParamValue::Blob(_)) {
+            unsafe { self.free_data(mem_mngr) };
+        }
+
         match value {
             ParamValue::Empty => self.vt = VariantType::Empty,
+            ParamValue::Null => self.vt = VariantType::Null,
             ParamValue::Bool(v) => self.update_to_bool(*v),
             ParamValue::I32(v) => self.update_to_i32(*v),
+            ParamValue::I64(v) => self.update_to_i64(*v),
+            ParamValue::U64(v) => self.update_to_u64(*v),
+            ParamValue::Error(v) => self.update_to_error(*v),
+            ParamValue::ClsId(v) => self.update_to_clsid(*v),
             ParamValue::F64(v) => self.update_to_f64(*v),
             ParamValue::Date(v) => self.update_to_date(*v),
             ParamValue::String(v) => {
-                let _ = unsafe { self.update_to_str(mem_mngr, v.as_slice()) };
+                unsafe { self.update_to_str(mem_mngr, v.as_slice()) }?;
             }
             ParamValue::Blob(v) => {
-                let _ = unsafe { self.update_to_blob(mem_mngr, v.as_slice()) };
+                unsafe { self.update_to_blob(mem_mngr, v.as_slice()) }?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Same as [`TVariant::update_from_return`], but strings and blobs,
+    /// written into memory of 1C platform, are passed without copying
+    pub fn update_from_platform(
+        &mut self,
+        mem_mngr: &MemoryManager,
+        value: PlatformValue,
+    ) -> Result<(), AllocationError> {
+        match value {
+            PlatformValue::Value(v) => self.update_from_return(mem_mngr, &v),
+            PlatformValue::String(v) => {
+                unsafe { self.update_to_platform_str(mem_mngr, v) }.map(|_| ())
+            }
+            PlatformValue::Blob(v) => {
+                unsafe { self.update_to_platform_blob(mem_mngr, v) }.map(|_| ())
             }
         }
     }
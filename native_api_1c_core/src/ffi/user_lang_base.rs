@@ -30,5 +30,7 @@ unsafe extern "system" fn set_user_interface_language_code<T: AddInWrapper>(
 ) {
     let component = unsafe { this.get_component() };
     let lang = unsafe { U16CStr::from_ptr_str(lang) };
-    component.addin.set_user_interface_language_code(lang)
+    component.guard("SetUserInterfaceLanguageCode", (), |component| {
+        component.addin.set_user_interface_language_code(lang)
+    })
 }
@@ -0,0 +1,91 @@
+/// Names up to this length (in UTF-16 code units, including NUL-terminator)
+/// are folded without allocation
+const INLINE_LEN: usize = 64;
+
+/// Folds UTF-16 code unit to lower case, as 1C compares names of properties
+/// and methods ignoring case. Covers ASCII, Latin-1 and Cyrillic letters,
+/// including `Ё`/`ё` and other letters of `Ѐ`-`Џ` range, that are used in 1C
+/// identifiers. Other code units are returned as is. It is `const`, so that
+/// folded names are built and checked for collisions at compile time
+pub const fn fold_case(c: u16) -> u16 {
+    match c {
+        // A-Z
+        0x0041..=0x005A => c + 0x20,
+        // À-Þ, except ×
+        0x00C0..=0x00DE if c != 0x00D7 => c + 0x20,
+        // Ѐ-Џ, including Ё
+        0x0400..=0x040F => c + 0x50,
+        // А-Я
+        0x0410..=0x042F => c + 0x20,
+        _ => c,
+    }
+}
+
+/// Folds every code unit of `name` with [`fold_case`]. `N` must be equal to
+/// the length of `name`, it is used to build folded names at compile time:
+/// ```
+/// use native_api_1c_core::{interface::fold_name, widestring::u16cstr};
+///
+/// const NAME: &[u16] = u16cstr!("Имя").as_slice_with_nul();
+/// const FOLDED: [u16; NAME.len()] = fold_name(NAME);
+/// assert_eq!(&FOLDED, u16cstr!("имя").as_slice_with_nul());
+/// ```
+pub const fn fold_name<const N: usize>(name: &[u16]) -> [u16; N] {
+    assert!(name.len() == N, "length of name must be equal to N");
+    let mut folded = [0; N];
+    let mut i = 0;
+    while i < N {
+        folded[i] = fold_case(name[i]);
+        i += 1;
+    }
+    folded
+}
+
+/// Whether names are equal, ignoring case, see [`fold_case`]
+pub const fn eq_ignore_case(a: &[u16], b: &[u16]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if fold_case(a[i]) != fold_case(b[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Name, passed by 1C platform, folded with [`fold_case`] to be matched
+/// against names, folded with [`fold_name`]. Short names are kept on stack
+pub struct FoldedName {
+    inline: [u16; INLINE_LEN],
+    heap: Vec<u16>,
+    len: usize,
+}
+
+impl FoldedName {
+    pub fn new(name: &[u16]) -> Self {
+        let mut folded = Self {
+            inline: [0; INLINE_LEN],
+            heap: Vec::new(),
+            len: name.len(),
+        };
+        if name.len() <= INLINE_LEN {
+            for (dst, src) in folded.inline.iter_mut().zip(name) {
+                *dst = fold_case(*src);
+            }
+        } else {
+            folded.heap = name.iter().copied().map(fold_case).collect();
+        }
+        folded
+    }
+
+    pub fn as_slice(&self) -> &[u16] {
+        if self.len <= INLINE_LEN {
+            &self.inline[..self.len]
+        } else {
+            &self.heap
+        }
+    }
+}
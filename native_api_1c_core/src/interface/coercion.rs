@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+
+use super::{ParamType, ParamValue};
+
+/// Rules of converting [`ParamValue`] to a type, that differs from its
+/// variant, e.g. 1C platform passes `5` as `Int32` even to a `Float`
+/// parameter.
+///
+/// | From              | To                 | `Numeric` | `Strings` |
+/// |-------------------|--------------------|-----------|-----------|
+/// | `I32`/`I64`/`U64` | `F64`              | yes       | yes       |
+/// | integral `F64`    | `I32`/`I64`/`U64`  | in range  | in range  |
+/// | `I32`/`I64`/`U64` | narrower integer   | in range  | in range  |
+/// | `Bool`            | `I32`/`I64`/`U64`  | `0`/`1`   | `0`/`1`   |
+/// | `I32`/`I64`/`U64` | `Bool`             | `!= 0`    | `!= 0`    |
+/// | `String`          | number             | no        | parsed    |
+///
+/// Strings are parsed in invariant format: `.` as decimal separator, no group
+/// separators, e.g. `"-1234.5"`. Values, that do not match the table, are
+/// left as is, so conversion to the target type fails
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Coercion {
+    /// No conversions besides ones of `ParamValue::to_*` methods
+    Strict,
+    /// Numbers and booleans are converted to each other without loss
+    #[default]
+    Numeric,
+    /// Same as `Numeric`, and strings are parsed as numbers
+    Strings,
+}
+
+impl ParamValue {
+    /// Converts value to `ty` according to `coercion` table
+    /// # Returns
+    /// `Cow<ParamValue>` - converted value, or the value itself, if it
+    /// already has type `ty` or cannot be converted
+    pub fn coerce_to(
+        &self,
+        ty: ParamType,
+        coercion: Coercion,
+    ) -> Cow<'_, ParamValue> {
+        match self.coerced(ty, coercion) {
+            Some(value) => Cow::Owned(value),
+            None => Cow::Borrowed(self),
+        }
+    }
+
+    /// Same as [`ParamValue::coerce_to`], but takes the value by value
+    pub fn coerce_into(self, ty: ParamType, coercion: Coercion) -> Self {
+        self.coerced(ty, coercion).unwrap_or(self)
+    }
+
+    fn coerced(&self, ty: ParamType, coercion: Coercion) -> Option<Self> {
+        let is_number = matches!(
+            ty,
+            ParamType::I32 | ParamType::I64 | ParamType::U64 | ParamType::F64
+        );
+
+        match (coercion, self) {
+            (Coercion::Strict, _) => None,
+            (Coercion::Strings, ParamValue::String(v)) if is_number => {
+                let number = parse_number(&v.to_string_lossy())?;
+                Some(number.coerced(ty, Coercion::Numeric).unwrap_or(number))
+            }
+            (_, value) => value.coerced_number(ty),
+        }
+    }
+
+    /// `to_i64` and `to_u64` accept other numbers themselves
+    fn coerced_number(&self, ty: ParamType) -> Option<Self> {
+        match (ty, self) {
+            (ParamType::Bool, ParamValue::I32(v)) => Some(Self::Bool(*v != 0)),
+            (ParamType::Bool, ParamValue::I64(v)) => Some(Self::Bool(*v != 0)),
+            (ParamType::Bool, ParamValue::U64(v)) => Some(Self::Bool(*v != 0)),
+
+            (ParamType::I32, ParamValue::Bool(v)) => {
+                Some(Self::I32((*v).into()))
+            }
+            (
+                ParamType::I32,
+                ParamValue::I64(_) | ParamValue::U64(_) | ParamValue::F64(_),
+            ) => self
+                .to_i64()
+                .and_then(|v| i32::try_from(v).ok())
+                .map(Self::I32),
+
+            (ParamType::I64, ParamValue::Bool(v)) => {
+                Some(Self::I64((*v).into()))
+            }
+
+            (ParamType::U64, ParamValue::Bool(v)) => {
+                Some(Self::U64((*v).into()))
+            }
+
+            (ParamType::F64, ParamValue::I32(v)) => {
+                Some(Self::F64((*v).into()))
+            }
+            (ParamType::F64, ParamValue::I64(v)) => Some(Self::F64(*v as f64)),
+            (ParamType::F64, ParamValue::U64(v)) => Some(Self::F64(*v as f64)),
+
+            _ => None,
+        }
+    }
+}
+
+/// Integers are parsed exactly, so that large values are not rounded by `f64`
+fn parse_number(value: &str) -> Option<ParamValue> {
+    let value = value.trim();
+    if let Ok(v) = value.parse::<i64>() {
+        return Some(ParamValue::I64(v));
+    }
+    if let Ok(v) = value.parse::<u64>() {
+        return Some(ParamValue::U64(v));
+    }
+
+    let is_float = !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b));
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|v| is_float && v.is_finite())
+        .map(ParamValue::F64)
+}
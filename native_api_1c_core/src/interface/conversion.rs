@@ -0,0 +1,256 @@
+use std::{error::Error, fmt};
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use widestring::{U16Str, U16String};
+
+use super::{AddInError, ParamValue, ParamValues};
+use crate::ffi::provided_types::Tm;
+
+/// Error of converting [`ParamValue`] to a Rust type
+/// # Fields
+/// * `expected` - name of the Rust type, e.g. `"i32"`
+/// * `actual` - name of the variant, e.g. `"String"`, see
+///   [`ParamValue::variant_name`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConversionError {
+    pub expected: &'static str,
+    pub actual: &'static str,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected `{}`, got `{}`", self.expected, self.actual)
+    }
+}
+
+impl Error for ConversionError {}
+
+impl ParamValue {
+    /// Name of the variant, used in [`ConversionError`]
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ParamValue::Empty => "Empty",
+            ParamValue::Null => "Null",
+            ParamValue::Bool(_) => "Bool",
+            ParamValue::I32(_) => "I32",
+            ParamValue::I64(_) => "I64",
+            ParamValue::U64(_) => "U64",
+            ParamValue::F64(_) => "F64",
+            ParamValue::Date(_) => "Date",
+            ParamValue::String(_) => "String",
+            ParamValue::Blob(_) => "Blob",
+            ParamValue::Error(_) => "Error",
+            ParamValue::ClsId(_) => "ClsId",
+        }
+    }
+}
+
+/// `None` becomes `ParamValue::Empty` (`Undefined` in 1C)
+macro_rules! impl_from {
+    ($($ty:ty => |$value:ident| $convert:expr),* $(,)?) => {$(
+        impl From<$ty> for ParamValue {
+            fn from($value: $ty) -> Self {
+                $convert
+            }
+        }
+
+        impl From<Option<$ty>> for ParamValue {
+            fn from(value: Option<$ty>) -> Self {
+                value.map_or(Self::Empty, Self::from)
+            }
+        }
+    )*};
+}
+
+impl_from! {
+    bool => |v| Self::Bool(v),
+    i8 => |v| Self::I32(v.into()),
+    i16 => |v| Self::I32(v.into()),
+    i32 => |v| Self::I32(v),
+    i64 => |v| Self::I64(v),
+    u8 => |v| Self::I32(v.into()),
+    u16 => |v| Self::I32(v.into()),
+    u32 => |v| Self::I64(v.into()),
+    u64 => |v| Self::U64(v),
+    f32 => |v| Self::F64(v.into()),
+    f64 => |v| Self::F64(v),
+    String => |v| Self::from_str(v),
+    &str => |v| Self::from_str(v),
+    U16String => |v| Self::String(v),
+    Vec<u8> => |v| Self::Blob(v),
+    &[u8] => |v| Self::Blob(v.to_vec()),
+    Tm => |v| Self::Date(v),
+    NaiveDateTime => |v| Self::Date(v.into()),
+    DateTime<FixedOffset> => |v| Self::Date(v.into()),
+    DateTime<Utc> => |v| Self::Date(v.into()),
+}
+
+/// Conversion from a reference, `ParamValue::Empty` and `ParamValue::Null`
+/// become `None`. Type is named in errors with `as "name"`, if it has
+/// a lifetime
+macro_rules! impl_try_from_ref {
+    ($($ty:ty $(as $name:literal)? => |$value:ident| $convert:expr),* $(,)?) => {$(
+        impl<'a> TryFrom<&'a ParamValue> for $ty {
+            type Error = ConversionError;
+
+            fn try_from($value: &'a ParamValue) -> Result<Self, Self::Error> {
+                let converted: Option<$ty> = $convert;
+                converted.ok_or(ConversionError {
+                    expected: impl_try_from_ref!(@name $ty $(, $name)?),
+                    actual: $value.variant_name(),
+                })
+            }
+        }
+
+        impl<'a> TryFrom<&'a ParamValue> for Option<$ty> {
+            type Error = ConversionError;
+
+            fn try_from(value: &'a ParamValue) -> Result<Self, Self::Error> {
+                match value {
+                    ParamValue::Empty | ParamValue::Null => Ok(None),
+                    value => <$ty>::try_from(value).map(Some),
+                }
+            }
+        }
+    )*};
+    (@name $ty:ty) => {
+        stringify!($ty)
+    };
+    (@name $ty:ty, $name:literal) => {
+        $name
+    };
+}
+
+/// Conversion from a reference and from a value. Value is converted as a
+/// reference, unless it can be moved into the type with `; |v| into`
+macro_rules! impl_try_from {
+    ($($ty:ty => |$value:ident| $convert:expr $(; |$owned:ident| $into:expr)?),* $(,)?) => {$(
+        impl_try_from_ref! { $ty => |$value| $convert }
+
+        impl TryFrom<ParamValue> for $ty {
+            type Error = ConversionError;
+
+            fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+                impl_try_from!(@owned value, $ty $(, |$owned| $into)?)
+            }
+        }
+
+        impl TryFrom<ParamValue> for Option<$ty> {
+            type Error = ConversionError;
+
+            fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+                match value {
+                    ParamValue::Empty | ParamValue::Null => Ok(None),
+                    value => <$ty>::try_from(value).map(Some),
+                }
+            }
+        }
+    )*};
+    (@owned $value:ident, $ty:ty) => {
+        <$ty>::try_from(&$value)
+    };
+    (@owned $value:ident, $ty:ty, |$owned:ident| $into:expr) => {{
+        let error = ConversionError {
+            expected: stringify!($ty),
+            actual: $value.variant_name(),
+        };
+        let $owned = $value;
+        let converted: Option<$ty> = $into;
+        converted.ok_or(error)
+    }};
+}
+
+/// Accepts `F64` and integer variants, that `f64` represents exactly, as
+/// integers accept `F64` without fractional part
+fn to_f64_exact(value: &ParamValue) -> Option<f64> {
+    // `f64` to `i128` conversion is exact for integers of 64 bits
+    let exact = |float: f64, int: i128| (float as i128 == int).then_some(float);
+    match value {
+        ParamValue::I32(v) => Some((*v).into()),
+        ParamValue::I64(v) => exact(*v as f64, (*v).into()),
+        ParamValue::U64(v) => exact(*v as f64, (*v).into()),
+        value => value.to_f64(),
+    }
+}
+
+// integers are converted from any integer variant, if they fit into the type
+impl_try_from! {
+    bool => |v| v.to_bool(),
+    i8 => |v| v.to_i64().and_then(|v| v.try_into().ok()),
+    i16 => |v| v.to_i64().and_then(|v| v.try_into().ok()),
+    i32 => |v| v.to_i64().and_then(|v| v.try_into().ok()),
+    i64 => |v| v.to_i64(),
+    u8 => |v| v.to_u64().and_then(|v| v.try_into().ok()),
+    u16 => |v| v.to_u64().and_then(|v| v.try_into().ok()),
+    u32 => |v| v.to_u64().and_then(|v| v.try_into().ok()),
+    u64 => |v| v.to_u64(),
+    // precision is lost, as in `as` conversion, but finite values stay finite
+    f32 => |v| to_f64_exact(v)
+        .filter(|v| !v.is_finite() || v.abs() <= f32::MAX.into())
+        .map(|v| v as f32),
+    f64 => |v| to_f64_exact(v),
+    String => |v| v.to_str(); |v| v.into_str(),
+    U16String => |v| match v {
+        ParamValue::String(v) => Some(v.clone()),
+        _ => None,
+    }; |v| match v {
+        ParamValue::String(v) => Some(v),
+        _ => None,
+    },
+    Vec<u8> => |v| v.to_blob(); |v| v.into_blob(),
+    Tm => |v| v.to_date(),
+    NaiveDateTime => |v| v.to_date().and_then(|v| v.try_into().ok()),
+    DateTime<FixedOffset> => |v| v.to_date_tz(),
+    DateTime<Utc> => |v| v.to_date_utc(),
+}
+
+// borrow the value, e.g. from `ParamValues::get`, without copying it
+impl_try_from_ref! {
+    &'a U16Str as "&U16Str" => |v| match v {
+        ParamValue::String(v) => Some(v.as_ustr()),
+        _ => None,
+    },
+    &'a [u8] as "&[u8]" => |v| match v {
+        ParamValue::Blob(v) => Some(v.as_slice()),
+        _ => None,
+    },
+}
+
+impl ParamValues {
+    /// Converts parameter to a Rust type. Parameter is not copied, so
+    /// `&U16Str` and `&[u8]` borrow it, owned types copy it once
+    /// # Arguments
+    /// * `index` - index of the parameter
+    /// # Returns
+    /// `Result<T, AddInError>` - converted value, or `invalid_param` error
+    /// with [`ConversionError`] as source
+    pub fn get<'a, T>(&'a self, index: usize) -> Result<T, AddInError>
+    where
+        T: TryFrom<&'a ParamValue, Error = ConversionError>,
+    {
+        if index >= self.len() {
+            return Err(AddInError::invalid_param(index));
+        }
+        T::try_from(&self[index])
+            .map_err(|err| AddInError::invalid_param(index).with_source(err))
+    }
+
+    /// Sets parameter, e.g. an out parameter
+    /// # Arguments
+    /// * `index` - index of the parameter
+    /// * `value` - new value
+    /// # Returns
+    /// `Result<(), AddInError>` - `invalid_param` error if there is no such
+    /// parameter
+    pub fn set(
+        &mut self,
+        index: usize,
+        value: impl Into<ParamValue>,
+    ) -> Result<(), AddInError> {
+        if index >= self.len() {
+            return Err(AddInError::invalid_param(index));
+        }
+        self[index] = value.into();
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use std::{error::Error, fmt};
+
+use rust_decimal::Decimal;
+use widestring::U16String;
+
+use super::ParamValue;
+
+/// Error of converting [`ParamValue`] to [`Decimal`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecimalError {
+    /// Value is out of `Decimal` range (about ±7.9e28)
+    Overflow(String),
+    /// Value has more than 28 fractional digits
+    PrecisionLoss(String),
+    /// String is not a number in invariant format, e.g. `"-1234.56"`
+    Invalid(String),
+    /// Value is neither a number nor a string
+    UnexpectedType,
+}
+
+impl fmt::Display for DecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow(value) => {
+                write!(f, "decimal overflow: `{value}` is out of range")
+            }
+            Self::PrecisionLoss(value) => write!(
+                f,
+                "decimal precision loss: `{value}` has too many fractional digits"
+            ),
+            Self::Invalid(value) => write!(
+                f,
+                "invalid decimal: `{value}`, expected number with `.` as separator"
+            ),
+            Self::UnexpectedType => {
+                write!(f, "invalid decimal: expected number or string")
+            }
+        }
+    }
+}
+
+impl Error for DecimalError {}
+
+/// Parses a decimal from its wire form, rejecting values that cannot be
+/// represented exactly
+fn parse_decimal(value: &str) -> Result<Decimal, DecimalError> {
+    let trimmed = value.trim();
+    let parsed = if trimmed.contains(['e', 'E']) {
+        Decimal::from_scientific(trimmed)
+    } else {
+        Decimal::from_str_exact(trimmed)
+    };
+
+    parsed.map_err(|err| match err {
+        rust_decimal::Error::ExceedsMaximumPossibleValue
+        | rust_decimal::Error::LessThanMinimumPossibleValue => {
+            DecimalError::Overflow(value.to_string())
+        }
+        // scale is also exceeded by large positive exponents, e.g. `1e40`
+        rust_decimal::Error::ScaleExceedsMaximumPrecision(_)
+            if has_positive_exponent(trimmed) =>
+        {
+            DecimalError::Overflow(value.to_string())
+        }
+        rust_decimal::Error::Underflow
+        | rust_decimal::Error::ScaleExceedsMaximumPrecision(_) => {
+            DecimalError::PrecisionLoss(value.to_string())
+        }
+        // parser reports too many digits with a generic error, so a well
+        // formed number, that is not parsed, can only be out of range
+        rust_decimal::Error::ErrorString(_) if is_number(trimmed) => {
+            DecimalError::Overflow(value.to_string())
+        }
+        rust_decimal::Error::ErrorString(_)
+        | rust_decimal::Error::ConversionTo(_) => {
+            DecimalError::Invalid(value.to_string())
+        }
+    })
+}
+
+/// Checks that value is a number in invariant format, optionally with
+/// exponent and `_` group separators, as accepted by `rust_decimal`
+fn is_number(value: &str) -> bool {
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (value, None),
+    };
+    let is_digits = |part: &str| {
+        part.starts_with(|c: char| c.is_ascii_digit())
+            && part.chars().all(|c| c.is_ascii_digit() || c == '_')
+    };
+
+    let mantissa = mantissa.strip_prefix(['+', '-']).unwrap_or(mantissa);
+    let mantissa_valid = match mantissa.split_once('.') {
+        Some(("", fract)) => is_digits(fract),
+        Some((int, fract)) => {
+            is_digits(int) && (fract.is_empty() || is_digits(fract))
+        }
+        None => is_digits(mantissa),
+    };
+    let exponent_valid = exponent.is_none_or(|exponent| {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        !exponent.is_empty() && exponent.chars().all(|c| c.is_ascii_digit())
+    });
+
+    mantissa_valid && exponent_valid
+}
+
+fn has_positive_exponent(value: &str) -> bool {
+    value
+        .split_once(['e', 'E'])
+        .and_then(|(_, exponent)| exponent.parse::<i32>().ok())
+        .is_some_and(|exponent| exponent > 0)
+}
+
+/// Native API has no decimal type, so decimals are passed as strings in
+/// invariant format: `.` as decimal separator, no group separators, e.g.
+/// `"-1234.56"`. In 1C such string is produced by `XMLString(Number)`
+/// (`XMLСтрока(Число)`) and parsed back by `Number(String)` (`Число(Строка)`).
+/// Whole numbers and doubles are accepted as well, doubles are converted by
+/// their shortest representation, so `0.1` becomes exactly `0.1`
+impl ParamValue {
+    pub fn from_decimal(val: impl Into<Decimal>) -> Self {
+        Self::String(U16String::from_str(&val.into().to_string()))
+    }
+
+    /// Converts value to `Decimal`, describing why it is not possible
+    pub fn try_to_decimal(&self) -> Result<Decimal, DecimalError> {
+        match self {
+            ParamValue::I32(v) => Ok((*v).into()),
+            ParamValue::I64(v) => Ok((*v).into()),
+            ParamValue::U64(v) => Ok((*v).into()),
+            ParamValue::F64(v) if v.is_finite() => {
+                parse_decimal(&v.to_string())
+            }
+            ParamValue::F64(v) => Err(DecimalError::Invalid(v.to_string())),
+            ParamValue::String(v) => parse_decimal(&v.to_string_lossy()),
+            _ => Err(DecimalError::UnexpectedType),
+        }
+    }
+
+    pub fn into_decimal(self) -> Option<Decimal> {
+        self.try_to_decimal().ok()
+    }
+
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        self.try_to_decimal().ok()
+    }
+
+    pub fn to_optional_decimal(
+        &self,
+        none_value: &ParamValue,
+    ) -> Option<Option<Decimal>> {
+        if self == none_value {
+            Some(None)
+        } else {
+            Self::to_decimal(self).map(Some)
+        }
+    }
+
+    pub fn set_decimal(&mut self, val: impl Into<Decimal>) {
+        *self = Self::from_decimal(val);
+    }
+}
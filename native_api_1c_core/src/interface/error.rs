@@ -0,0 +1,152 @@
+use std::{error::Error, ffi::c_long, fmt};
+
+use crate::ffi::{connection::MessageCode, memory_manager::AllocationError};
+
+/// Error, returned from AddIn methods. When returned to FFI layer, it is
+/// reported to 1C platform with `Connection::add_error`, so its description
+/// becomes available in 1C as `ErrorDescription()` (`ОписаниеОшибки()`).
+/// # Fields
+/// * `message` - error description
+/// * `source` - optional underlying error, appended to description
+/// * `code` - message code, see [MessageCode](crate::ffi::connection::MessageCode)
+/// * `scode` - error code, passed to `AddError`. Non-zero value makes
+///   1C platform raise an exception with error description
+#[derive(Debug)]
+pub struct AddInError {
+    message: String,
+    source: Option<Box<dyn Error + Send + Sync>>,
+    code: MessageCode,
+    scode: c_long,
+}
+
+impl AddInError {
+    /// `scode` used by default, makes 1C platform raise an exception
+    pub const DEFAULT_SCODE: c_long = 1;
+
+    /// Creates a new error with given message, `MessageCode::Fail` code
+    /// and [`AddInError::DEFAULT_SCODE`]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            source: None,
+            code: MessageCode::Fail,
+            scode: Self::DEFAULT_SCODE,
+        }
+    }
+
+    /// Creates a new error from another error, using its `Display` as message
+    pub fn from_error(error: impl Error + Send + Sync + 'static) -> Self {
+        Self::new(error.to_string()).with_source(error)
+    }
+
+    /// Sets underlying error
+    pub fn with_source(
+        mut self,
+        source: impl Into<Box<dyn Error + Send + Sync>>,
+    ) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Sets message code
+    pub fn with_code(mut self, code: MessageCode) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Sets `scode`, passed to `AddError`
+    pub fn with_scode(mut self, scode: c_long) -> Self {
+        self.scode = scode;
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn code(&self) -> MessageCode {
+        self.code
+    }
+
+    pub fn scode(&self) -> c_long {
+        self.scode
+    }
+
+    /// Full error description, as it is reported to 1C platform: message
+    /// followed by messages of all underlying errors
+    pub fn description(&self) -> String {
+        let mut description = self.message.clone();
+        let mut source = self.source();
+        while let Some(error) = source {
+            let error_message = error.to_string();
+            if !description.ends_with(&error_message) {
+                description.push_str(": ");
+                description.push_str(&error_message);
+            }
+            source = error.source();
+        }
+        description
+    }
+
+    /// Error for call of method, that does not exist or cannot be called
+    /// this way. Index is 0-based, as passed by 1C platform, and is shown
+    /// 1-based, as in 1C
+    pub fn method_not_found(method_num: usize) -> Self {
+        Self::new(format!("method #{} not found", method_num + 1))
+    }
+
+    /// Error for access to property, that does not exist or cannot be
+    /// accessed this way. Index is shown 1-based
+    pub fn prop_not_found(prop_num: usize) -> Self {
+        Self::new(format!("property #{} not found", prop_num + 1))
+    }
+
+    /// Error for parameter value of unexpected type. Index is shown 1-based
+    pub fn invalid_param(param_num: usize) -> Self {
+        Self::new(format!("invalid value of parameter #{}", param_num + 1))
+    }
+
+    /// Error for property value of unexpected type. Index is shown 1-based
+    pub fn invalid_prop_value(prop_num: usize) -> Self {
+        Self::new(format!("invalid value for property #{}", prop_num + 1))
+    }
+}
+
+impl fmt::Display for AddInError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for AddInError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+/// Kept for methods returning `Result<T, ()>`, error has no details
+impl From<()> for AddInError {
+    fn from(_: ()) -> Self {
+        Self::new("method call error")
+    }
+}
+
+impl From<String> for AddInError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<&str> for AddInError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<AllocationError> for AddInError {
+    fn from(error: AllocationError) -> Self {
+        Self::from_error(error)
+    }
+}
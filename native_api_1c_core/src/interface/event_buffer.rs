@@ -0,0 +1,203 @@
+use std::{
+    collections::VecDeque,
+    ffi::c_long,
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::ffi::connection::Connection;
+
+/// Settings of [`EventBuffer`]
+/// # Fields
+/// * `depth` - event buffer depth, set on creation if current depth of
+///   1C platform buffer is smaller
+/// * `max_depth` - when 1C platform rejects an event, buffer depth is doubled
+///   until it reaches this value
+/// * `overflow_capacity` - number of events kept locally while 1C platform
+///   buffer is full, oldest events are dropped when it is exceeded
+#[derive(Clone, Copy, Debug)]
+pub struct EventBufferConfig {
+    pub depth: Option<c_long>,
+    pub max_depth: Option<c_long>,
+    pub overflow_capacity: usize,
+}
+
+impl Default for EventBufferConfig {
+    fn default() -> Self {
+        Self {
+            depth: None,
+            max_depth: None,
+            overflow_capacity: 1024,
+        }
+    }
+}
+
+/// External event, waiting in local overflow queue
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalEvent {
+    pub source: String,
+    pub name: String,
+    pub data: String,
+}
+
+/// Counters of [`EventBuffer`]
+/// # Fields
+/// * `sent` - events accepted by 1C platform
+/// * `queued` - events waiting in local overflow queue
+/// * `dropped` - events dropped because overflow queue was full
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EventBufferStats {
+    pub sent: u64,
+    pub queued: usize,
+    pub dropped: u64,
+}
+
+/// Sends external events to 1C platform without silently losing them.
+///
+/// `Connection::external_event` returns `false` when event buffer of 1C
+/// platform is full. In this case `EventBuffer` grows the buffer up to
+/// `max_depth` and keeps the event in local overflow queue, delivering it
+/// before any later event. Events are dropped only when the overflow queue
+/// is full, and every dropped event is counted.
+///
+/// `EventBuffer` can be shared between threads, e.g. in an `Arc`.
+pub struct EventBuffer {
+    connection: &'static Connection,
+    max_depth: Option<c_long>,
+    overflow_capacity: usize,
+    overflow: Mutex<VecDeque<ExternalEvent>>,
+    sent: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl EventBuffer {
+    /// Creates a new buffer and sets 1C platform event buffer depth,
+    /// if it is configured
+    /// # Arguments
+    /// * `connection` - connection with 1C platform
+    /// * `config` - buffer settings
+    pub fn new(
+        connection: &'static Connection,
+        config: EventBufferConfig,
+    ) -> Self {
+        if let Some(depth) = config.depth
+            && connection.get_event_buffer_depth() < depth
+        {
+            connection.set_event_buffer_depth(depth);
+        }
+
+        Self {
+            connection,
+            max_depth: config.max_depth,
+            overflow_capacity: config.overflow_capacity,
+            overflow: Mutex::new(VecDeque::new()),
+            sent: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Sends an external event to 1C platform. Events, that were not accepted
+    /// earlier, are sent first, so the order of events is preserved
+    /// # Arguments
+    /// * `source` - name of the event source
+    /// * `name` - name of the event
+    /// * `data` - data of the event
+    /// # Returns
+    /// `bool` - `true` if the event was accepted by 1C platform, `false` if
+    /// it was queued or dropped
+    pub fn send(&self, source: &str, name: &str, data: &str) -> bool {
+        let mut overflow = self.lock_overflow();
+        self.flush_locked(&mut overflow);
+
+        if overflow.is_empty() && self.try_send(source, name, data) {
+            return true;
+        }
+
+        if self.overflow_capacity == 0 {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        if overflow.len() >= self.overflow_capacity {
+            overflow.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        overflow.push_back(ExternalEvent {
+            source: source.to_string(),
+            name: name.to_string(),
+            data: data.to_string(),
+        });
+
+        false
+    }
+
+    /// Retries sending of queued events
+    /// # Returns
+    /// `usize` - number of events accepted by 1C platform
+    pub fn flush(&self) -> usize {
+        let mut overflow = self.lock_overflow();
+        self.flush_locked(&mut overflow)
+    }
+
+    /// Discards all events, that are not yet processed by 1C platform, both
+    /// in platform buffer and in local overflow queue. Discarded events are not
+    /// counted as dropped
+    pub fn clean(&self) {
+        self.lock_overflow().clear();
+        self.connection.clean_event_buffer();
+    }
+
+    /// Current counters of the buffer
+    pub fn stats(&self) -> EventBufferStats {
+        EventBufferStats {
+            sent: self.sent.load(Ordering::Relaxed),
+            queued: self.lock_overflow().len(),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    fn lock_overflow(
+        &self,
+    ) -> std::sync::MutexGuard<'_, VecDeque<ExternalEvent>> {
+        self.overflow.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn flush_locked(&self, overflow: &mut VecDeque<ExternalEvent>) -> usize {
+        let mut delivered = 0;
+        while let Some(event) = overflow.front() {
+            if !self.try_send(&event.source, &event.name, &event.data) {
+                break;
+            }
+            overflow.pop_front();
+            delivered += 1;
+        }
+        delivered
+    }
+
+    fn try_send(&self, source: &str, name: &str, data: &str) -> bool {
+        let mut accepted = self.connection.external_event(source, name, data);
+        if !accepted && self.grow_depth() {
+            accepted = self.connection.external_event(source, name, data);
+        }
+
+        if accepted {
+            self.sent.fetch_add(1, Ordering::Relaxed);
+        }
+        accepted
+    }
+
+    fn grow_depth(&self) -> bool {
+        let Some(max_depth) = self.max_depth else {
+            return false;
+        };
+
+        let depth = self.connection.get_event_buffer_depth();
+        if depth >= max_depth {
+            return false;
+        }
+
+        let new_depth = depth.saturating_mul(2).max(depth + 1).min(max_depth);
+        self.connection.set_event_buffer_depth(new_depth)
+    }
+}
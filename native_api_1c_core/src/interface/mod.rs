@@ -1,13 +1,43 @@
+mod case_folding;
+mod coercion;
+mod conversion;
+mod decimal;
+mod error;
+mod event_buffer;
 mod param_type;
 mod param_value;
+mod param_value_ref;
+mod platform_value;
+#[cfg(feature = "serde")]
+mod serialization;
+mod status_line;
 
-use crate::ffi::{connection::Connection,};
+use crate::ffi::{
+    api_version::{ApiVersion, PStrEncoding},
+    connection::Connection,
+    memory_manager::MemoryManager,
+};
 use widestring::U16CStr;
+pub use case_folding::{eq_ignore_case, fold_case, fold_name, FoldedName};
+pub use coercion::Coercion;
+pub use conversion::ConversionError;
+pub use decimal::DecimalError;
+pub use error::AddInError;
+pub use event_buffer::{
+    EventBuffer, EventBufferConfig, EventBufferStats, ExternalEvent,
+};
 pub use param_type::{ParamType};
 pub use param_value::{ParamValue, ParamValues};
+pub use param_value_ref::{ParamValueRef, ParamValuesRef};
+pub use platform_value::PlatformValue;
+#[cfg(feature = "serde")]
+pub use serialization::DeserializeError;
+pub use status_line::{ProgressFormat, ProgressReporter, StatusLineGuard};
 
 
-pub type AddInWrapperResult<T> = Result<T, ()>;
+/// Result of AddIn methods. Error is reported to 1C platform with
+/// `Connection::add_error` and the call fails with an exception
+pub type AddInWrapperResult<T> = Result<T, AddInError>;
 
 /// `AddInWrapper` trait is used to implement the 1C AddIn interface,
 /// and is used in FFI to get necessary information about the AddIn
@@ -18,7 +48,6 @@ pub type AddInWrapperResult<T> = Result<T, ()>;
 ///
 /// Many of them are equivalents of methods in the 1C AddIn interface, and their
 /// descriptions can be found in the [1C documentation](https://its.1c.ru/db/metod8dev/content/3221/hdoc).
-#[allow(clippy::result_unit_err)]
 pub trait AddInWrapper {
     /// Equivalent to `Init` from Native API interface and is called when the AddIn is loaded by 1C platform
     /// and is used to pass the pointer to the 1C Connection object
@@ -28,13 +57,32 @@ pub trait AddInWrapper {
     /// `bool` - operation success status
     fn init(&mut self, interface: &'static Connection) -> bool;
 
+    /// Equivalent to `setMemManager` from Native API interface and is called by 1C platform
+    /// before `Init` to pass the pointer to the 1C MemoryManager object. AddIn needs it
+    /// only for `Connection` methods, that exchange memory with 1C platform
+    /// # Arguments
+    /// * `mem` - pointer to the 1C MemoryManager object
+    /// # Returns
+    /// `bool` - operation success status
+    fn set_mem_manager(&mut self, mem: &'static MemoryManager) -> bool {
+        let _ = mem;
+        true
+    }
+
     /// Equivalent to `GetInfo` from Native API interface and is used to get Native API version used by AddIn, either
-    /// `1000` meaning 1.0 or `2000` meaning 2.0. It will be later removed to only
-    /// support 2.0 version.
+    /// `1000` meaning 1.0 or `2000` meaning 2.0. With 1.0 strings are passed to 1C platform as `PStr`
+    /// instead of `WStr`, see [`ApiVersion`]
     /// # Returns
     /// `u16` - Native API version
     fn get_info(&self) -> u16 {
-        2000
+        ApiVersion::V2.info()
+    }
+
+    /// Encoding of single-byte `PStr` strings, see [`PStrEncoding`]. Strings
+    /// are written as `PStr` only with Native API 1.0, but `PStr` passed by
+    /// the host is decoded with it in any version
+    fn pstr_encoding(&self) -> PStrEncoding {
+        PStrEncoding::Utf8
     }
 
     /// Equivalent to `Done` from Native API interface and is called when the AddIn is unloaded by 1C platform
@@ -184,6 +232,43 @@ pub trait AddInWrapper {
         params: &mut ParamValues,
     ) -> AddInWrapperResult<ParamValue>;
 
+    /// Same as [`AddInWrapper::call_as_proc`], but parameters borrow memory
+    /// of 1C platform for the duration of the call. It is the method, called
+    /// by 1C platform. Default implementation copies parameters and calls
+    /// `call_as_proc`, override it to avoid copying of large strings and
+    /// blobs
+    /// # Arguments
+    /// * `method_num` - index of method
+    /// * `params` - borrowed parameters, only modified ones are written back
+    /// # Returns
+    /// `AddInWrapperResult<()>` - operation result
+    fn call_as_proc_ref(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValuesRef,
+    ) -> AddInWrapperResult<()> {
+        params.with_owned(|params| self.call_as_proc(method_num, params))
+    }
+
+    /// Same as [`AddInWrapper::call_as_func`], but parameters borrow memory
+    /// of 1C platform for the duration of the call, see
+    /// [`AddInWrapper::call_as_proc_ref`], and result can be written directly
+    /// into memory of 1C platform, see [`PlatformValue`]
+    /// # Arguments
+    /// * `method_num` - index of method
+    /// * `params` - borrowed parameters, only modified ones are written back
+    /// # Returns
+    /// `AddInWrapperResult<PlatformValue>` - result of the method
+    fn call_as_func_ref(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValuesRef,
+    ) -> AddInWrapperResult<PlatformValue> {
+        params
+            .with_owned(|params| self.call_as_func(method_num, params))
+            .map(PlatformValue::from)
+    }
+
     /// Equivalent to `SetLocale` from Native API interface and is used to set the locale
     /// of the AddIn. It's marked as deprecated in 1C documentation, but is still available
     /// for use with platform versions prior to 8.3.21
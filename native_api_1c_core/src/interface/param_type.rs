@@ -4,9 +4,18 @@
 pub enum ParamType {
     Bool,
     I32,
+    I64,
+    U64,
     F64,
+    Decimal,
     String,
     Date,
+    /// Date, where 1C empty date is `None`
+    OptionalDate,
+    /// Date with offset, local time of the process
+    DateTz,
+    /// Date in UTC
+    DateUtc,
     Blob,
 }
 
@@ -1,26 +1,43 @@
 use std::ops::{Index, IndexMut};
 
 use crate::ffi::{provided_types::Tm};
-use widestring::U16CString;
+use widestring::U16String;
 use super::param_type::ParamType;
 
 /// Represents 1C variant values for parameters in safe Rust code.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParamValue {
     /// Empty value
     Empty,
+    /// `Null` value
+    Null,
     /// Boolean value
     Bool(bool),
     /// Integer value
     I32(i32),
+    /// 64-bit integer value
+    I64(i64),
+    /// 64-bit unsigned integer value
+    U64(u64),
     /// Float value
     F64(f64),
     /// Date-time value
     Date(Tm),
-    /// UTF-16 string value
-    String(U16CString),
+    /// UTF-16 string value. Length is kept exactly, including embedded NULs,
+    /// NUL-terminator is added only when the string is passed to 1C platform
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "super::serialization::u16_string")
+    )]
+    String(U16String),
     /// Blob value
+    #[cfg_attr(feature = "serde", serde(with = "super::serialization::blob"))]
     Blob(Vec<u8>),
+    /// Error code, passed as `Error` or `HResult` value
+    Error(i32),
+    /// UUID value
+    ClsId([u8; 16]),
 }
 
 impl ParamValue {
@@ -36,6 +53,16 @@ impl ParamValue {
         Self::I32(val.into())
     }
 
+    pub fn from_i64(val: impl Into<i64>) -> Self {
+        Self::I64(val.into())
+    }
+
+    /// Takes `u64` itself, so that unsuffixed literals (e.g. default values
+    /// in `#[arg(...)]`) are inferred as `u64` instead of `i32`
+    pub fn from_u64(val: u64) -> Self {
+        Self::U64(val)
+    }
+
     pub fn from_f64(val: impl Into<f64>) -> Self {
         Self::F64(val.into())
     }
@@ -44,8 +71,24 @@ impl ParamValue {
         Self::Date(val.into())
     }
 
+    /// `None` becomes 1C empty date, see [`Tm::EMPTY_DATE`]
+    pub fn from_nullable_date(val: Option<chrono::NaiveDateTime>) -> Self {
+        Self::Date(Tm::from_optional_date_time(val))
+    }
+
+    /// Date-time is converted to local time of the process, see [`Tm`]
+    pub fn from_date_tz(val: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        Self::Date(val.into())
+    }
+
+    /// Date-time is converted to local time of the process, see [`Tm`]
+    pub fn from_date_utc(val: chrono::DateTime<chrono::Utc>) -> Self {
+        Self::Date(val.into())
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(val: impl AsRef<str>) -> Self {
-        Self::String(U16CString::from_str_truncate(val))
+        Self::String(U16String::from_str(val.as_ref()))
     }
 
     pub fn from_blob(val: impl Into<Vec<u8>>) -> Self {
@@ -68,6 +111,14 @@ impl ParamValue {
         }
     }
 
+    pub fn into_i64(self) -> Option<i64> {
+        self.to_i64()
+    }
+
+    pub fn into_u64(self) -> Option<u64> {
+        self.to_u64()
+    }
+
     pub fn into_f64(self) -> Option<f64> {
         match self {
             ParamValue::F64(v) => Some(v),
@@ -82,6 +133,18 @@ impl ParamValue {
         }
     }
 
+    pub fn into_nullable_date(self) -> Option<Option<chrono::NaiveDateTime>> {
+        self.to_nullable_date()
+    }
+
+    pub fn into_date_tz(self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.to_date_tz()
+    }
+
+    pub fn into_date_utc(self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.to_date_utc()
+    }
+
     pub fn into_str(self) -> Option<String> {
         match self {
             ParamValue::String(v) => Some(v.to_string_lossy()),
@@ -112,6 +175,43 @@ impl ParamValue {
         }
     }
 
+    /// Accepts any integer value, that fits into `i64`, and `F64` without
+    /// fractional part, as 1C platform passes numbers, that do not fit into
+    /// `i32`, as `Double`
+    pub fn to_i64(&self) -> Option<i64> {
+        match self {
+            ParamValue::I32(v) => Some((*v).into()),
+            ParamValue::I64(v) => Some(*v),
+            ParamValue::U64(v) => i64::try_from(*v).ok(),
+            // `i64::MAX as f64` is 2^63, which is already out of range
+            ParamValue::F64(v)
+                if v.fract() == 0.0
+                    && *v >= i64::MIN as f64
+                    && *v < i64::MAX as f64 =>
+            {
+                Some(*v as i64)
+            }
+            _ => None
+        }
+    }
+
+    /// Accepts any non-negative integer value, that fits into `u64`, and
+    /// `F64` without fractional part, see [`ParamValue::to_i64`]
+    pub fn to_u64(&self) -> Option<u64> {
+        match self {
+            ParamValue::I32(v) => u64::try_from(*v).ok(),
+            ParamValue::I64(v) => u64::try_from(*v).ok(),
+            ParamValue::U64(v) => Some(*v),
+            // `u64::MAX as f64` is 2^64, which is already out of range
+            ParamValue::F64(v)
+                if v.fract() == 0.0 && *v >= 0.0 && *v < u64::MAX as f64 =>
+            {
+                Some(*v as u64)
+            }
+            _ => None
+        }
+    }
+
     pub fn to_f64(&self) -> Option<f64> {
         match self {
             ParamValue::F64(v) => Some(*v),
@@ -126,6 +226,29 @@ impl ParamValue {
         }
     }
 
+    /// 1C empty date becomes `Some(None)`, dates out of range become `None`
+    pub fn to_nullable_date(&self) -> Option<Option<chrono::NaiveDateTime>> {
+        match self {
+            ParamValue::Date(v) => v.to_optional_date_time().ok(),
+            _ => None
+        }
+    }
+
+    /// Date is treated as local time of the process, unless 1C platform
+    /// provides its offset, see [`Tm::to_date_time_tz`]
+    pub fn to_date_tz(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        match self {
+            ParamValue::Date(v) => v.to_date_time_tz().ok(),
+            _ => None
+        }
+    }
+
+    /// Date is treated as local time of the process, unless 1C platform
+    /// provides its offset, see [`Tm::to_date_time_tz`]
+    pub fn to_date_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.to_date_tz().map(|v| v.to_utc())
+    }
+
     pub fn to_str(&self) -> Option<String> {
         match self {
             ParamValue::String(v) => Some(v.to_string_lossy()),
@@ -147,7 +270,7 @@ impl ParamValue {
             Some(None)
         } else {
             Self::to_bool(self)
-                .map( |value| Some(value) )
+                .map(Some)
         }
     }
 
@@ -156,7 +279,25 @@ impl ParamValue {
             Some(None)
         } else {
             Self::to_i32(self)
-                .map( |value| Some(value) )
+                .map(Some)
+        }
+    }
+
+    pub fn to_optional_i64(&self, none_value: &ParamValue) -> Option<Option<i64>> {
+        if self == none_value {
+            Some(None)
+        } else {
+            Self::to_i64(self)
+                .map(Some)
+        }
+    }
+
+    pub fn to_optional_u64(&self, none_value: &ParamValue) -> Option<Option<u64>> {
+        if self == none_value {
+            Some(None)
+        } else {
+            Self::to_u64(self)
+                .map(Some)
         }
     }
 
@@ -165,7 +306,7 @@ impl ParamValue {
             Some(None)
         } else {
             Self::to_f64(self)
-                .map( |value| Some(value) )
+                .map(Some)
         }
     }
 
@@ -174,7 +315,43 @@ impl ParamValue {
             Some(None)
         } else {
             Self::to_date(self)
-                .map( |value| Some(value) )
+                .map(Some)
+        }
+    }
+
+    pub fn to_optional_nullable_date(
+        &self,
+        none_value: &ParamValue,
+    ) -> Option<Option<Option<chrono::NaiveDateTime>>> {
+        if self == none_value {
+            Some(None)
+        } else {
+            Self::to_nullable_date(self)
+                .map(Some)
+        }
+    }
+
+    pub fn to_optional_date_tz(
+        &self,
+        none_value: &ParamValue,
+    ) -> Option<Option<chrono::DateTime<chrono::FixedOffset>>> {
+        if self == none_value {
+            Some(None)
+        } else {
+            Self::to_date_tz(self)
+                .map(Some)
+        }
+    }
+
+    pub fn to_optional_date_utc(
+        &self,
+        none_value: &ParamValue,
+    ) -> Option<Option<chrono::DateTime<chrono::Utc>>> {
+        if self == none_value {
+            Some(None)
+        } else {
+            Self::to_date_utc(self)
+                .map(Some)
         }
     }
 
@@ -183,7 +360,7 @@ impl ParamValue {
             Some(None)
         } else {
             Self::to_str(self)
-                .map( |value| Some(value) )
+                .map(Some)
         }
     }
 
@@ -192,7 +369,7 @@ impl ParamValue {
             Some(None)
         } else {
             Self::to_blob(self)
-                .map( |value| Some(value) )
+                .map(Some)
         }
     }
 
@@ -206,6 +383,14 @@ impl ParamValue {
         *self = Self::I32(val.into());
     }
 
+    pub fn set_i64(&mut self, val: impl Into<i64>) {
+        *self = Self::I64(val.into());
+    }
+
+    pub fn set_u64(&mut self, val: impl Into<u64>) {
+        *self = Self::U64(val.into());
+    }
+
     pub fn set_f64(&mut self, val: impl Into<f64>) {
         *self = Self::F64(val.into());
     }
@@ -214,8 +399,20 @@ impl ParamValue {
         *self = Self::Date(val.into());
     }
 
+    pub fn set_nullable_date(&mut self, val: Option<chrono::NaiveDateTime>) {
+        *self = Self::from_nullable_date(val);
+    }
+
+    pub fn set_date_tz(&mut self, val: chrono::DateTime<chrono::FixedOffset>) {
+        *self = Self::from_date_tz(val);
+    }
+
+    pub fn set_date_utc(&mut self, val: chrono::DateTime<chrono::Utc>) {
+        *self = Self::from_date_utc(val);
+    }
+
     pub fn set_str(&mut self, val: impl AsRef<str>) {
-        *self = Self::String(U16CString::from_str_truncate(val));
+        *self = Self::String(U16String::from_str(val.as_ref()));
     }
 
     pub fn set_blob(&mut self, val: impl Into<Vec<u8>>) {
@@ -236,12 +433,30 @@ impl ParamValue {
             ParamType::I32 => {
                 "from_i32"
             }
+            ParamType::I64 => {
+                "from_i64"
+            }
+            ParamType::U64 => {
+                "from_u64"
+            }
             ParamType::F64 => {
                 "from_f64"
             }
+            ParamType::Decimal => {
+                "from_decimal"
+            }
             ParamType::Date => {
                 "from_date"
             }
+            ParamType::OptionalDate => {
+                "from_nullable_date"
+            }
+            ParamType::DateTz => {
+                "from_date_tz"
+            }
+            ParamType::DateUtc => {
+                "from_date_utc"
+            }
             ParamType::String => {
                 "from_str"
             }
@@ -259,12 +474,30 @@ impl ParamValue {
             ParamType::I32 => {
                 "into_i32"
             }
+            ParamType::I64 => {
+                "into_i64"
+            }
+            ParamType::U64 => {
+                "into_u64"
+            }
             ParamType::F64 => {
                 "into_f64"
             }
+            ParamType::Decimal => {
+                "into_decimal"
+            }
             ParamType::Date => {
                 "into_date"
             }
+            ParamType::OptionalDate => {
+                "into_nullable_date"
+            }
+            ParamType::DateTz => {
+                "into_date_tz"
+            }
+            ParamType::DateUtc => {
+                "into_date_utc"
+            }
             ParamType::String => {
                 "into_str"
             }
@@ -282,12 +515,30 @@ impl ParamValue {
             ParamType::I32 => {
                 "to_i32"
             }
+            ParamType::I64 => {
+                "to_i64"
+            }
+            ParamType::U64 => {
+                "to_u64"
+            }
             ParamType::F64 => {
                 "to_f64"
             }
+            ParamType::Decimal => {
+                "to_decimal"
+            }
             ParamType::Date => {
                 "to_date"
             }
+            ParamType::OptionalDate => {
+                "to_nullable_date"
+            }
+            ParamType::DateTz => {
+                "to_date_tz"
+            }
+            ParamType::DateUtc => {
+                "to_date_utc"
+            }
             ParamType::String => {
                 "to_str"
             }
@@ -305,12 +556,30 @@ impl ParamValue {
             ParamType::I32 => {
                 "to_optional_i32"
             }
+            ParamType::I64 => {
+                "to_optional_i64"
+            }
+            ParamType::U64 => {
+                "to_optional_u64"
+            }
             ParamType::F64 => {
                 "to_optional_f64"
             }
+            ParamType::Decimal => {
+                "to_optional_decimal"
+            }
             ParamType::Date => {
                 "to_optional_date"
             }
+            ParamType::OptionalDate => {
+                "to_optional_nullable_date"
+            }
+            ParamType::DateTz => {
+                "to_optional_date_tz"
+            }
+            ParamType::DateUtc => {
+                "to_optional_date_utc"
+            }
             ParamType::String => {
                 "to_optional_str"
             }
@@ -328,12 +597,30 @@ impl ParamValue {
             ParamType::I32 => {
                 "set_i32"
             }
+            ParamType::I64 => {
+                "set_i64"
+            }
+            ParamType::U64 => {
+                "set_u64"
+            }
             ParamType::F64 => {
                 "set_f64"
             }
+            ParamType::Decimal => {
+                "set_decimal"
+            }
             ParamType::Date => {
                 "set_date"
             }
+            ParamType::OptionalDate => {
+                "set_nullable_date"
+            }
+            ParamType::DateTz => {
+                "set_date_tz"
+            }
+            ParamType::DateUtc => {
+                "set_date_utc"
+            }
             ParamType::String => {
                 "set_str"
             }
@@ -348,12 +635,17 @@ impl PartialEq for ParamValue {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Empty, Self::Empty) => true,
+            (Self::Null, Self::Null) => true,
             (Self::Bool(a), Self::Bool(b)) => a == b,
             (Self::I32(a), Self::I32(b)) => a == b,
+            (Self::I64(a), Self::I64(b)) => a == b,
+            (Self::U64(a), Self::U64(b)) => a == b,
             (Self::F64(a), Self::F64(b)) => a == b,
             (Self::Date(a), Self::Date(b)) => a == b,
             (Self::String(a), Self::String(b)) => a == b,
             (Self::Blob(a), Self::Blob(b)) => a == b,
+            (Self::Error(a), Self::Error(b)) => a == b,
+            (Self::ClsId(a), Self::ClsId(b)) => a == b,
             _ => false,
         }
     }
@@ -362,14 +654,20 @@ impl PartialEq for ParamValue {
 /// Represents 1C variant values for return values in safe Rust code.
 /// Only creator of the object can set the initial value, therefor has
 /// control over count of values.
+///
+/// Values, accessed mutably (with `IndexMut` or `ParamValues::set`), are
+/// marked as modified. Only modified values are written back to 1C platform
+/// after the call, so input strings and blobs are not copied again
 #[derive(Clone)]
 pub struct ParamValues {
     values: Vec<ParamValue>,
+    modified: Vec<bool>,
 }
 
 impl ParamValues {
     pub fn new(values: Vec<ParamValue>) -> Self {
-        Self { values }
+        let modified = vec![false; values.len()];
+        Self { values, modified }
     }
 
     pub fn len(&self) -> usize {
@@ -383,6 +681,30 @@ impl ParamValues {
     pub fn iter(&self) -> std::slice::Iter<'_, ParamValue> {
         self.values.iter()
     }
+
+    /// Whether the value was accessed mutably since `ParamValues` creation
+    pub fn is_modified(&self, index: usize) -> bool {
+        self.modified.get(index).copied().unwrap_or(false)
+    }
+
+    /// Iterates over modified values with their indices, see
+    /// [`ParamValues::is_modified`]
+    pub fn iter_modified(&self) -> impl Iterator<Item = (usize, &ParamValue)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.modified[*index])
+    }
+
+    /// Takes modified values with their indices
+    pub fn into_modified(self) -> Vec<(usize, ParamValue)> {
+        self.values
+            .into_iter()
+            .zip(self.modified)
+            .enumerate()
+            .filter_map(|(index, (value, modified))| modified.then_some((index, value)))
+            .collect()
+    }
 }
 
 impl Index<usize> for ParamValues {
@@ -395,6 +717,8 @@ impl Index<usize> for ParamValues {
 
 impl IndexMut<usize> for ParamValues {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.values[index]
+        let value = &mut self.values[index];
+        self.modified[index] = true;
+        value
     }
 }
@@ -0,0 +1,227 @@
+use std::borrow::Cow;
+
+use widestring::U16Str;
+
+use super::{AddInError, ParamValue, ParamValues};
+use crate::ffi::provided_types::Tm;
+
+/// Same as [`ParamValue`], but strings and blobs borrow memory of 1C
+/// platform (or of `ParamValue`) instead of copying it. Strings are `Cow`,
+/// because single-byte `PStr` values are converted to UTF-16
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamValueRef<'a> {
+    /// Empty value
+    Empty,
+    /// `Null` value
+    Null,
+    /// Boolean value
+    Bool(bool),
+    /// Integer value
+    I32(i32),
+    /// 64-bit integer value
+    I64(i64),
+    /// 64-bit unsigned integer value
+    U64(u64),
+    /// Float value
+    F64(f64),
+    /// Date-time value
+    Date(Tm),
+    /// UTF-16 string value
+    String(Cow<'a, U16Str>),
+    /// Blob value
+    Blob(&'a [u8]),
+    /// Error code, passed as `Error` or `HResult` value
+    Error(i32),
+    /// UUID value
+    ClsId([u8; 16]),
+}
+
+impl<'a> ParamValueRef<'a> {
+    /// Copies borrowed data into [`ParamValue`]
+    pub fn into_owned(self) -> ParamValue {
+        match self {
+            Self::Empty => ParamValue::Empty,
+            Self::Null => ParamValue::Null,
+            Self::Bool(v) => ParamValue::Bool(v),
+            Self::I32(v) => ParamValue::I32(v),
+            Self::I64(v) => ParamValue::I64(v),
+            Self::U64(v) => ParamValue::U64(v),
+            Self::F64(v) => ParamValue::F64(v),
+            Self::Date(v) => ParamValue::Date(v),
+            Self::String(v) => ParamValue::String(v.into_owned()),
+            Self::Blob(v) => ParamValue::Blob(v.to_vec()),
+            Self::Error(v) => ParamValue::Error(v),
+            Self::ClsId(v) => ParamValue::ClsId(v),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&U16Str> {
+        match self {
+            Self::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Unlike [`ParamValueRef::as_str`], result is not bound to the value
+    /// itself, but to the memory it borrows
+    pub fn into_str(self) -> Option<Cow<'a, U16Str>> {
+        match self {
+            Self::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_blob(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Blob(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl ParamValue {
+    /// Borrows the value as [`ParamValueRef`]
+    pub fn as_value_ref(&self) -> ParamValueRef<'_> {
+        match self {
+            Self::Empty => ParamValueRef::Empty,
+            Self::Null => ParamValueRef::Null,
+            Self::Bool(v) => ParamValueRef::Bool(*v),
+            Self::I32(v) => ParamValueRef::I32(*v),
+            Self::I64(v) => ParamValueRef::I64(*v),
+            Self::U64(v) => ParamValueRef::U64(*v),
+            Self::F64(v) => ParamValueRef::F64(*v),
+            Self::Date(v) => ParamValueRef::Date(*v),
+            Self::String(v) => ParamValueRef::String(Cow::Borrowed(v)),
+            Self::Blob(v) => ParamValueRef::Blob(v),
+            Self::Error(v) => ParamValueRef::Error(*v),
+            Self::ClsId(v) => ParamValueRef::ClsId(*v),
+        }
+    }
+}
+
+impl<'a> From<&'a ParamValue> for ParamValueRef<'a> {
+    fn from(value: &'a ParamValue) -> Self {
+        value.as_value_ref()
+    }
+}
+
+/// Same as [`ParamValues`], but values borrow memory of 1C platform for
+/// the duration of the call. Values, set by the AddIn, are kept as owned
+/// [`ParamValue`] and are written back to 1C platform after the call
+#[derive(Clone, Debug)]
+pub struct ParamValuesRef<'a> {
+    values: Vec<ParamValueRef<'a>>,
+    modified: Vec<Option<ParamValue>>,
+}
+
+impl<'a> ParamValuesRef<'a> {
+    pub fn new(values: Vec<ParamValueRef<'a>>) -> Self {
+        let modified = vec![None; values.len()];
+        Self { values, modified }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Current value of the parameter, borrowed from 1C platform or from
+    /// the value, set by the AddIn
+    pub fn value(&self, index: usize) -> Option<ParamValueRef<'_>> {
+        match self.modified.get(index)? {
+            Some(value) => Some(value.as_value_ref()),
+            None => self.values.get(index).cloned(),
+        }
+    }
+
+    /// Mutable access to the parameter, e.g. an out parameter. Value is
+    /// copied on first access and marked as modified
+    pub fn value_mut(&mut self, index: usize) -> Option<&mut ParamValue> {
+        let value = self.values.get(index)?;
+        let modified = &mut self.modified[index];
+        Some(modified.get_or_insert_with(|| value.clone().into_owned()))
+    }
+
+    /// Sets parameter, e.g. an out parameter
+    /// # Returns
+    /// `Result<(), AddInError>` - `invalid_param` error if there is no such
+    /// parameter
+    pub fn set(
+        &mut self,
+        index: usize,
+        value: impl Into<ParamValue>,
+    ) -> Result<(), AddInError> {
+        if index >= self.len() {
+            return Err(AddInError::invalid_param(index));
+        }
+        self.modified[index] = Some(value.into());
+        Ok(())
+    }
+
+    /// Whether the parameter was set or accessed mutably
+    pub fn is_modified(&self, index: usize) -> bool {
+        matches!(self.modified.get(index), Some(Some(_)))
+    }
+
+    /// Iterates over modified values with their indices
+    pub fn iter_modified(&self) -> impl Iterator<Item = (usize, &ParamValue)> {
+        self.modified
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| Some((index, value.as_ref()?)))
+    }
+
+    /// Takes modified values with their indices, releasing borrowed memory
+    pub fn into_modified(self) -> Vec<(usize, ParamValue)> {
+        self.modified
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, value)| Some((index, value?)))
+            .collect()
+    }
+
+    /// Copies parameters into [`ParamValues`] for `f`, then keeps values,
+    /// modified by `f`. Used to call methods, that take owned parameters
+    pub fn with_owned<R>(
+        &mut self,
+        f: impl FnOnce(&mut ParamValues) -> R,
+    ) -> R {
+        let values = (0..self.len())
+            .filter_map(|index| self.value(index))
+            .map(ParamValueRef::into_owned)
+            .collect();
+        let mut owned = ParamValues::new(values);
+
+        let result = f(&mut owned);
+        for (index, value) in owned.into_modified() {
+            self.modified[index] = Some(value);
+        }
+        result
+    }
+}
+
+impl<'a> From<&'a ParamValues> for ParamValuesRef<'a> {
+    fn from(values: &'a ParamValues) -> Self {
+        Self::new(values.iter().map(ParamValue::as_value_ref).collect())
+    }
+}
+
+impl ParamValues {
+    /// Borrows parameters as [`ParamValuesRef`] for `f`, then writes back
+    /// values, modified by `f`. Used to call methods, that take borrowed
+    /// parameters
+    pub fn with_ref<R>(
+        &mut self,
+        f: impl FnOnce(&mut ParamValuesRef<'_>) -> R,
+    ) -> R {
+        let mut params = ParamValuesRef::from(&*self);
+        let result = f(&mut params);
+        for (index, value) in params.into_modified() {
+            self[index] = value;
+        }
+        result
+    }
+}
@@ -0,0 +1,48 @@
+use widestring::U16String;
+
+use super::ParamValue;
+use crate::ffi::platform_buf::{PlatformBuf, PlatformStr};
+
+/// Value, returned to 1C platform: either [`ParamValue`], that is copied
+/// into memory of 1C platform, or a string or blob, already written there
+/// with [`PlatformStr`] or [`PlatformBuf`]. Large results are then allocated
+/// only once
+#[derive(Debug)]
+pub enum PlatformValue {
+    /// Value, copied into memory of 1C platform
+    Value(ParamValue),
+    /// UTF-16 string in memory of 1C platform
+    String(PlatformStr),
+    /// Blob in memory of 1C platform
+    Blob(PlatformBuf),
+}
+
+impl PlatformValue {
+    /// Copies string or blob from memory of 1C platform into [`ParamValue`],
+    /// e.g. for methods, that return owned values
+    pub fn into_param_value(self) -> ParamValue {
+        match self {
+            Self::Value(v) => v,
+            Self::String(v) => ParamValue::String(U16String::from(v.as_ustr())),
+            Self::Blob(v) => ParamValue::Blob(v.as_slice().to_vec()),
+        }
+    }
+}
+
+impl From<ParamValue> for PlatformValue {
+    fn from(value: ParamValue) -> Self {
+        Self::Value(value)
+    }
+}
+
+impl From<PlatformStr> for PlatformValue {
+    fn from(value: PlatformStr) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<PlatformBuf> for PlatformValue {
+    fn from(value: PlatformBuf) -> Self {
+        Self::Blob(value)
+    }
+}
@@ -0,0 +1,443 @@
+use std::{error::Error, fmt, iter::Enumerate, slice};
+
+use chrono::{FixedOffset, NaiveDateTime};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{
+        self, DeserializeOwned, IntoDeserializer, SeqAccess, Visitor,
+        value::SeqDeserializer,
+    },
+    forward_to_deserialize_any, ser,
+};
+
+use super::{AddInError, ParamValue, ParamValues};
+use crate::ffi::provided_types::Tm;
+
+/// Format of `ParamValue::Date`, when it is decoded with
+/// [`ParamValues::decode`], accepted by `chrono` types
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// `ParamValue::String` is a string in human-readable formats, if it is valid
+/// UTF-16, otherwise it is a sequence of UTF-16 code units
+pub(super) mod u16_string {
+    use std::fmt;
+
+    use serde::{Deserializer, Serializer, de};
+    use widestring::U16String;
+
+    pub fn serialize<S: Serializer>(
+        value: &U16String,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value.to_string() {
+            Ok(value) if serializer.is_human_readable() => {
+                serializer.serialize_str(&value)
+            }
+            _ => serializer.collect_seq(value.as_slice()),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<U16String, D::Error> {
+        struct U16StringVisitor;
+
+        impl<'de> de::Visitor<'de> for U16StringVisitor {
+            type Value = U16String;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string or a sequence of UTF-16 code units")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<U16String, E> {
+                Ok(U16String::from_str(v))
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<U16String, A::Error> {
+                let mut units =
+                    Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(unit) = seq.next_element::<u16>()? {
+                    units.push(unit);
+                }
+                Ok(U16String::from_vec(units))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(U16StringVisitor)
+        } else {
+            deserializer.deserialize_seq(U16StringVisitor)
+        }
+    }
+}
+
+/// `ParamValue::Blob` is a base64 string in human-readable formats and
+/// bytes otherwise
+pub(super) mod blob {
+    use std::fmt;
+
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use serde::{Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(
+        value: &[u8],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&STANDARD.encode(value))
+        } else {
+            serializer.serialize_bytes(value)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        struct BlobVisitor;
+
+        impl<'de> de::Visitor<'de> for BlobVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a base64 string or bytes")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+                STANDARD.decode(v).map_err(E::custom)
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+                Ok(v.to_vec())
+            }
+
+            fn visit_byte_buf<E: de::Error>(
+                self,
+                v: Vec<u8>,
+            ) -> Result<Vec<u8>, E> {
+                Ok(v)
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Vec<u8>, A::Error> {
+                let mut bytes =
+                    Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                Ok(bytes)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BlobVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(BlobVisitor)
+        }
+    }
+}
+
+/// `Tm` is an ISO 8601 string in [`DATE_FORMAT`], e.g.
+/// `"2024-05-17T12:30:00"`. Offset from UTC is appended, e.g.
+/// `"2024-05-17T12:30:00+03:00"`, when 1C platform provides it in `gmtoff`
+/// (unix only), so that raw C `struct tm` fields are never exposed
+impl Serialize for Tm {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let date = NaiveDateTime::try_from(self).map_err(ser::Error::custom)?;
+        let date = date.format(DATE_FORMAT);
+
+        #[cfg(target_family = "unix")]
+        if self.gmtoff != 0 {
+            let offset = self.utc_offset().ok_or_else(|| {
+                ser::Error::custom(format!("invalid offset {}", self.gmtoff))
+            })?;
+            return serializer.collect_str(&format_args!("{date}{offset}"));
+        }
+        serializer.collect_str(&date)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tm {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct TmVisitor;
+
+        impl Visitor<'_> for TmVisitor {
+            type Value = Tm;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    f,
+                    "a date in `{DATE_FORMAT}` format, optionally with offset"
+                )
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Tm, E> {
+                let (date, offset) =
+                    NaiveDateTime::parse_and_remainder(v, DATE_FORMAT)
+                        .map_err(E::custom)?;
+                let offset = match offset {
+                    "" => None,
+                    offset => {
+                        Some(offset.parse::<FixedOffset>().map_err(E::custom)?)
+                    }
+                };
+
+                let tm = Tm::from(date);
+                // reject dates, that are not representable in 1C
+                NaiveDateTime::try_from(&tm).map_err(E::custom)?;
+
+                #[cfg(target_family = "unix")]
+                let tm = match offset {
+                    Some(offset) => Tm {
+                        gmtoff: offset.local_minus_utc().into(),
+                        ..tm
+                    },
+                    None => tm,
+                };
+                // `Tm` has no offset on Windows, date is kept as wall-clock
+                #[cfg(target_family = "windows")]
+                let _ = offset;
+                Ok(tm)
+            }
+        }
+
+        deserializer.deserialize_str(TmVisitor)
+    }
+}
+
+/// Error of decoding [`ParamValues`] into a Rust type
+/// # Fields
+/// * `index` - index of the parameter, that failed to decode, `None` if the
+///   error is not related to a single parameter, e.g. parameter count
+///   mismatch
+/// * `message` - error description
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeserializeError {
+    pub index: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            index: None,
+            message: msg.to_string(),
+        }
+    }
+}
+
+impl From<DeserializeError> for AddInError {
+    fn from(error: DeserializeError) -> Self {
+        match error.index {
+            Some(index) => AddInError::invalid_param(index).with_source(error),
+            None => AddInError::from_error(error),
+        }
+    }
+}
+
+/// Parameters are serialized as a sequence of values, modification flags are
+/// not kept
+impl Serialize for ParamValues {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for ParamValues {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Vec::<ParamValue>::deserialize(deserializer).map(Self::new)
+    }
+}
+
+impl ParamValues {
+    /// Decodes all parameters into a Rust type, e.g. a struct or a tuple
+    /// with a field for each parameter in order. Mapping of values is
+    /// described at `Deserializer` implementation for `&ParamValue`
+    /// # Returns
+    /// `Result<T, AddInError>` - decoded value, or `invalid_param` error
+    /// with [`DeserializeError`] as source
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, AddInError> {
+        T::deserialize(self).map_err(AddInError::from)
+    }
+}
+
+/// Parameters are decoded as a sequence, structs take them by position.
+/// All parameters must be consumed
+impl<'de> Deserializer<'de> for &ParamValues {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let mut seq = ParamValuesSeq {
+            iter: self.iter().enumerate(),
+        };
+        let value = visitor.visit_seq(&mut seq)?;
+        match seq.iter.len() {
+            0 => Ok(value),
+            remaining => Err(de::Error::invalid_length(
+                self.len(),
+                &format!("{} parameters", self.len() - remaining).as_str(),
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct ParamValuesSeq<'a> {
+    iter: Enumerate<slice::Iter<'a, ParamValue>>,
+}
+
+impl<'de> SeqAccess<'de> for ParamValuesSeq<'_> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some((index, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+        seed.deserialize(value)
+            .map(Some)
+            .map_err(|err| DeserializeError {
+                index: err.index.or(Some(index)),
+                ..err
+            })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Values are mapped to Rust types as follows:
+/// * `Empty` and `Null` - `()` or `None`
+/// * `Bool`, `I32`, `I64`, `U64`, `F64` - numbers of any type, that fits the
+///   value
+/// * `Date` - string `YYYY-MM-DDTHH:MM:SS`, e.g. for `chrono::NaiveDateTime`,
+///   empty date is `None`
+/// * `String` - string, or unit variant of an enum
+/// * `Blob` and `ClsId` - bytes, or sequence of bytes, e.g. `Vec<u8>`
+/// * `Error` - `i32`
+impl<'de> Deserializer<'de> for &ParamValue {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            ParamValue::Empty | ParamValue::Null => visitor.visit_unit(),
+            ParamValue::Bool(v) => visitor.visit_bool(*v),
+            ParamValue::I32(v) => visitor.visit_i32(*v),
+            ParamValue::I64(v) => visitor.visit_i64(*v),
+            ParamValue::U64(v) => visitor.visit_u64(*v),
+            ParamValue::F64(v) => visitor.visit_f64(*v),
+            ParamValue::Date(v) => {
+                let date =
+                    NaiveDateTime::try_from(v).map_err(de::Error::custom)?;
+                visitor.visit_string(date.format(DATE_FORMAT).to_string())
+            }
+            ParamValue::String(v) => {
+                visitor.visit_string(v.to_string().map_err(de::Error::custom)?)
+            }
+            ParamValue::Blob(v) => visitor.visit_bytes(v),
+            ParamValue::Error(v) => visitor.visit_i32(*v),
+            ParamValue::ClsId(v) => visitor.visit_bytes(v),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            ParamValue::Empty | ParamValue::Null => visitor.visit_none(),
+            ParamValue::Date(v) if v.is_empty_date() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            ParamValue::Blob(v) => visitor.visit_seq(bytes_seq(v)),
+            ParamValue::ClsId(v) => visitor.visit_seq(bytes_seq(v)),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            ParamValue::String(v) => {
+                let variant = v.to_string().map_err(de::Error::custom)?;
+                visitor.visit_enum(variant.into_deserializer())
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+fn bytes_seq(
+    bytes: &[u8],
+) -> SeqDeserializer<impl Iterator<Item = u8> + '_, DeserializeError> {
+    SeqDeserializer::new(bytes.iter().copied())
+}
@@ -0,0 +1,165 @@
+use std::time::{Duration, Instant};
+
+use crate::ffi::connection::Connection;
+
+/// Shows a message in the status bar of 1C client while it is alive and
+/// resets the status line when dropped, so that no stale message is left
+/// behind, even if the operation fails or panics
+pub struct StatusLineGuard<'a> {
+    connection: &'a Connection,
+}
+
+impl<'a> StatusLineGuard<'a> {
+    /// Creates a new guard and shows the message
+    /// # Arguments
+    /// * `connection` - connection with 1C platform
+    /// * `text` - message to show
+    pub fn new(connection: &'a Connection, text: &str) -> Self {
+        connection.set_status_line(text);
+        Self { connection }
+    }
+
+    /// Replaces the shown message
+    /// # Returns
+    /// `bool` - operation success status
+    pub fn set(&self, text: &str) -> bool {
+        self.connection.set_status_line(text)
+    }
+}
+
+impl Drop for StatusLineGuard<'_> {
+    fn drop(&mut self) {
+        self.connection.reset_status_line();
+    }
+}
+
+/// How progress is shown by [`ProgressReporter`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressFormat {
+    /// `"<text>: step N of M"`, or `"<text>: step N"` if total is unknown
+    Steps,
+    /// `"<text>: P%"`, falls back to `Steps` if total is unknown
+    Percent,
+}
+
+/// Reports progress of a long-running operation in the status bar of 1C
+/// client. Updates are throttled, so that reporting every step of a tight
+/// loop does not slow the operation down. Status line is reset when the
+/// reporter is dropped
+pub struct ProgressReporter<'a> {
+    status_line: StatusLineGuard<'a>,
+    text: String,
+    current: u64,
+    total: Option<u64>,
+    format: ProgressFormat,
+    min_interval: Duration,
+    last_update: Option<Instant>,
+}
+
+impl<'a> ProgressReporter<'a> {
+    /// Minimal interval between status line updates used by default
+    pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(200);
+
+    /// Creates a new reporter and shows initial progress
+    /// # Arguments
+    /// * `connection` - connection with 1C platform
+    /// * `text` - description of the operation
+    /// * `total` - total number of steps, if known
+    pub fn new(
+        connection: &'a Connection,
+        text: impl Into<String>,
+        total: Option<u64>,
+    ) -> Self {
+        let text = text.into();
+        let mut reporter = Self {
+            status_line: StatusLineGuard::new(connection, &text),
+            text,
+            current: 0,
+            total,
+            format: ProgressFormat::Steps,
+            min_interval: Self::DEFAULT_MIN_INTERVAL,
+            last_update: None,
+        };
+        reporter.update(true);
+        reporter
+    }
+
+    /// Sets how progress is shown
+    pub fn with_format(mut self, format: ProgressFormat) -> Self {
+        self.format = format;
+        self.update(true);
+        self
+    }
+
+    /// Sets minimal interval between status line updates
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Advances progress by one step
+    pub fn inc(&mut self) {
+        self.advance(1);
+    }
+
+    /// Advances progress by `steps` steps
+    pub fn advance(&mut self, steps: u64) {
+        self.set_position(self.current.saturating_add(steps));
+    }
+
+    /// Sets current step
+    pub fn set_position(&mut self, current: u64) {
+        self.current = current;
+        let finished = self.total.is_some_and(|total| current >= total);
+        self.update(finished);
+    }
+
+    /// Sets total number of steps
+    pub fn set_total(&mut self, total: Option<u64>) {
+        self.total = total;
+        self.update(true);
+    }
+
+    /// Replaces description of the operation
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.update(true);
+    }
+
+    pub fn position(&self) -> u64 {
+        self.current
+    }
+
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Message, that is shown in the status line for current progress
+    pub fn status_text(&self) -> String {
+        match (self.format, self.total) {
+            (ProgressFormat::Percent, Some(total)) => {
+                let percent = (u128::from(self.current.min(total)) * 100)
+                    .checked_div(u128::from(total))
+                    .unwrap_or(100);
+                format!("{}: {percent}%", self.text)
+            }
+            (_, Some(total)) => {
+                format!("{}: step {} of {total}", self.text, self.current)
+            }
+            (_, None) => format!("{}: step {}", self.text, self.current),
+        }
+    }
+
+    fn update(&mut self, force: bool) {
+        let now = Instant::now();
+        let due = self.last_update.is_none_or(|last_update| {
+            now.duration_since(last_update) >= self.min_interval
+        });
+        if !force && !due {
+            return;
+        }
+
+        self.status_line.set(&self.status_text());
+        self.last_update = Some(now);
+    }
+}
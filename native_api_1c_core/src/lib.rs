@@ -14,3 +14,5 @@ pub mod interface;
 
 /// Reexport widestring crate
 pub use widestring;
+/// Reexport rust_decimal crate, used for `Decimal` values
+pub use rust_decimal;
@@ -0,0 +1,47 @@
+use native_api_1c_core::{
+    interface::{FoldedName, eq_ignore_case, fold_case},
+    widestring::U16CString,
+};
+use rstest::rstest;
+
+fn units(s: &str) -> Vec<u16> {
+    U16CString::from_str_truncate(s).into_vec_with_nul()
+}
+
+#[rstest]
+#[case('A', 'a')]
+#[case('z', 'z')]
+#[case('É', 'é')]
+#[case('×', '×')]
+#[case('Ж', 'ж')]
+#[case('Я', 'я')]
+#[case('Ё', 'ё')]
+#[case('Є', 'є')]
+#[case('ё', 'ё')]
+#[case('1', '1')]
+#[case('_', '_')]
+fn test_fold_case(#[case] c: char, #[case] expected: char) {
+    assert_eq!(fold_case(c as u16), expected as u16);
+}
+
+#[rstest]
+#[case("Функция", "функция", true)]
+#[case("СЧЁТЧИК", "счётчик", true)]
+#[case("Счётчик", "Счетчик", false)]
+#[case("MyFunction", "MYFUNCTION", true)]
+#[case("MyFunction", "MyFunctio", false)]
+fn test_eq_ignore_case(
+    #[case] a: &str,
+    #[case] b: &str,
+    #[case] expected: bool,
+) {
+    assert_eq!(eq_ignore_case(&units(a), &units(b)), expected);
+}
+
+#[rstest]
+#[case("ПолучитьЗначение")]
+#[case(&"ДлинноеИмя".repeat(10))]
+fn test_folded_name(#[case] name: &str) {
+    let folded = FoldedName::new(&units(name));
+    assert_eq!(folded.as_slice(), units(&name.to_lowercase()));
+}
@@ -0,0 +1,83 @@
+use std::borrow::Cow;
+
+use native_api_1c_core::interface::{Coercion, ParamType, ParamValue};
+use rstest::rstest;
+
+#[rstest]
+#[case(ParamValue::I32(5), ParamType::F64, ParamValue::F64(5.0))]
+#[case(ParamValue::I64(-5), ParamType::F64, ParamValue::F64(-5.0))]
+#[case(ParamValue::U64(5), ParamType::F64, ParamValue::F64(5.0))]
+#[case(ParamValue::F64(5.0), ParamType::I32, ParamValue::I32(5))]
+#[case(ParamValue::I64(5), ParamType::I32, ParamValue::I32(5))]
+#[case(ParamValue::U64(5), ParamType::I32, ParamValue::I32(5))]
+#[case(ParamValue::Bool(true), ParamType::I32, ParamValue::I32(1))]
+#[case(ParamValue::Bool(false), ParamType::I64, ParamValue::I64(0))]
+#[case(ParamValue::Bool(true), ParamType::U64, ParamValue::U64(1))]
+#[case(ParamValue::I32(0), ParamType::Bool, ParamValue::Bool(false))]
+#[case(ParamValue::I32(1), ParamType::Bool, ParamValue::Bool(true))]
+#[case(ParamValue::I64(-2), ParamType::Bool, ParamValue::Bool(true))]
+#[case(ParamValue::U64(0), ParamType::Bool, ParamValue::Bool(false))]
+fn test_numeric(
+    #[case] value: ParamValue,
+    #[case] ty: ParamType,
+    #[case] expected: ParamValue,
+) {
+    assert_eq!(value.coerce_to(ty, Coercion::Numeric).as_ref(), &expected);
+    assert_eq!(value.coerce_to(ty, Coercion::Strings).as_ref(), &expected);
+    assert_eq!(value.clone().coerce_into(ty, Coercion::Numeric), expected);
+    assert!(matches!(
+        value.coerce_to(ty, Coercion::Strict),
+        Cow::Borrowed(_)
+    ));
+}
+
+#[rstest]
+#[case(ParamValue::F64(5.5), ParamType::I32)]
+#[case(ParamValue::F64(f64::NAN), ParamType::I32)]
+#[case(ParamValue::I64(i64::from(i32::MAX) + 1), ParamType::I32)]
+#[case(ParamValue::F64(1.0), ParamType::Bool)]
+#[case(ParamValue::Bool(true), ParamType::F64)]
+#[case(ParamValue::I32(5), ParamType::String)]
+#[case(ParamValue::from_str("5"), ParamType::I32)]
+#[case(ParamValue::I32(5), ParamType::I32)]
+fn test_numeric_not_coerced(#[case] value: ParamValue, #[case] ty: ParamType) {
+    assert!(matches!(
+        value.coerce_to(ty, Coercion::Numeric),
+        Cow::Borrowed(_)
+    ));
+}
+
+#[rstest]
+#[case("5", ParamType::I32, ParamValue::I32(5))]
+#[case(" -5 ", ParamType::I64, ParamValue::I64(-5))]
+#[case("18446744073709551615", ParamType::U64, ParamValue::U64(u64::MAX))]
+#[case("9007199254740993", ParamType::I64, ParamValue::I64(9007199254740993))]
+#[case("1234.5", ParamType::F64, ParamValue::F64(1234.5))]
+#[case("1e3", ParamType::I32, ParamValue::I32(1000))]
+#[case("5", ParamType::F64, ParamValue::F64(5.0))]
+fn test_strings(
+    #[case] value: &str,
+    #[case] ty: ParamType,
+    #[case] expected: ParamValue,
+) {
+    let value = ParamValue::from_str(value);
+    assert_eq!(value.coerce_to(ty, Coercion::Strings).as_ref(), &expected);
+}
+
+#[rstest]
+#[case("1,5", ParamType::F64)]
+#[case("1 000", ParamType::I32)]
+#[case("inf", ParamType::F64)]
+#[case("NaN", ParamType::F64)]
+#[case("", ParamType::I32)]
+#[case("5", ParamType::String)]
+#[case("1", ParamType::Bool)]
+fn test_strings_not_coerced(#[case] value: &str, #[case] ty: ParamType) {
+    let value = ParamValue::from_str(value);
+    assert_eq!(value.coerce_to(ty, Coercion::Strings).as_ref(), &value);
+}
+
+#[test]
+fn test_default_is_numeric() {
+    assert_eq!(Coercion::default(), Coercion::Numeric);
+}
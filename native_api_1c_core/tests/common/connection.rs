@@ -0,0 +1,271 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::{c_long, c_ushort, c_void},
+    ptr,
+};
+
+use native_api_1c_core::{
+    ffi::{
+        connection::{Connection, Interface},
+        provided_types::TVariant,
+    },
+    interface::ParamValue,
+    widestring::U16CStr,
+};
+
+use super::memory::MockMemoryManager;
+
+/// Same layout as `IAddInDefBaseEx` of 1C platform
+#[repr(C)]
+struct MockConnectionVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    add_error: unsafe extern "system" fn(
+        &MockConnection,
+        c_ushort,
+        *const u16,
+        *const u16,
+        c_long,
+    ) -> bool,
+    read: unsafe extern "system" fn(
+        &MockConnection,
+        *mut u16,
+        &mut TVariant,
+        *mut c_long,
+        *mut *mut u16,
+    ) -> bool,
+    write: unsafe extern "system" fn(
+        &MockConnection,
+        *mut u16,
+        &mut TVariant,
+    ) -> bool,
+    register_profile_as:
+        unsafe extern "system" fn(&MockConnection, *mut u16) -> bool,
+    set_event_buffer_depth:
+        unsafe extern "system" fn(&MockConnection, c_long) -> bool,
+    get_event_buffer_depth:
+        unsafe extern "system" fn(&MockConnection) -> c_long,
+    external_event: unsafe extern "system" fn(
+        &MockConnection,
+        *mut u16,
+        *mut u16,
+        *mut u16,
+    ) -> bool,
+    clean_event_buffer: unsafe extern "system" fn(&MockConnection),
+    set_status_line:
+        unsafe extern "system" fn(&MockConnection, *mut u16) -> bool,
+    reset_status_line: unsafe extern "system" fn(&MockConnection),
+    get_interface:
+        unsafe extern "system" fn(&MockConnection, Interface) -> *mut c_void,
+}
+
+static VTABLE: MockConnectionVTable = MockConnectionVTable {
+    dtor: 0,
+    #[cfg(target_family = "unix")]
+    dtor2: 0,
+    add_error,
+    read,
+    write,
+    register_profile_as,
+    set_event_buffer_depth,
+    get_event_buffer_depth,
+    external_event,
+    clean_event_buffer,
+    set_status_line,
+    reset_status_line,
+    get_interface,
+};
+
+/// Error, reported with `AddError`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReportedError {
+    pub code: c_ushort,
+    pub source: String,
+    pub description: String,
+    pub scode: c_long,
+}
+
+/// Event, sent with `ExternalEvent`
+pub type Event = (String, String, String);
+
+/// Records calls of the AddIn. Event buffer accepts events, while it has
+/// less than `event_buffer_depth` unprocessed events, as 1C platform does
+#[repr(C)]
+pub struct MockConnection {
+    vptr: &'static MockConnectionVTable,
+    /// used to pass values of `Read` and error descriptions
+    pub mem: Option<&'static MockMemoryManager>,
+    pub errors: RefCell<Vec<ReportedError>>,
+    pub profile_name: RefCell<Option<String>>,
+    pub profile: RefCell<HashMap<String, ParamValue>>,
+    /// error code and description, returned by `Read`
+    pub read_error: RefCell<Option<(c_long, Option<String>)>>,
+    pub event_buffer_depth: Cell<c_long>,
+    /// events, accepted but not yet processed by 1C platform
+    pub events: RefCell<Vec<Event>>,
+    /// `Some` for `SetStatusLine`, `None` for `ResetStatusLine`
+    pub status_line: RefCell<Vec<Option<String>>>,
+    /// interfaces, requested with `GetInterface`, none is provided
+    pub interfaces: RefCell<Vec<Interface>>,
+}
+
+impl MockConnection {
+    pub fn new(mem: Option<&'static MockMemoryManager>) -> Self {
+        Self {
+            vptr: &VTABLE,
+            mem,
+            errors: RefCell::default(),
+            profile_name: RefCell::default(),
+            profile: RefCell::default(),
+            read_error: RefCell::default(),
+            event_buffer_depth: Cell::new(1),
+            events: RefCell::default(),
+            status_line: RefCell::default(),
+            interfaces: RefCell::default(),
+        }
+    }
+
+    /// Connection, that lives as long as 1C platform passes it
+    pub fn leaked(mem: Option<&'static MockMemoryManager>) -> &'static Self {
+        Box::leak(Box::new(Self::new(mem)))
+    }
+
+    pub fn as_connection(&self) -> &Connection {
+        unsafe { &*(self as *const Self).cast::<Connection>() }
+    }
+
+    /// 1C platform processes all accepted events
+    pub fn process_events(&self) -> Vec<Event> {
+        self.events.take()
+    }
+}
+
+unsafe fn to_string(ptr: *const u16) -> String {
+    unsafe { U16CStr::from_ptr_str(ptr) }.to_string_lossy()
+}
+
+unsafe extern "system" fn add_error(
+    this: &MockConnection,
+    code: c_ushort,
+    source: *const u16,
+    description: *const u16,
+    scode: c_long,
+) -> bool {
+    this.errors.borrow_mut().push(ReportedError {
+        code,
+        source: unsafe { to_string(source) },
+        description: unsafe { to_string(description) },
+        scode,
+    });
+    true
+}
+
+unsafe extern "system" fn read(
+    this: &MockConnection,
+    name: *mut u16,
+    value: &mut TVariant,
+    error_code: *mut c_long,
+    error_description: *mut *mut u16,
+) -> bool {
+    let mem = this.mem.expect("memory manager is not set").as_mem();
+    if let Some((code, description)) = this.read_error.borrow().clone() {
+        unsafe { *error_code = code };
+        if let Some(description) = description {
+            let description: Vec<u16> =
+                description.encode_utf16().chain([0]).collect();
+            let ptr = mem.alloc_str(description.len()).unwrap().as_ptr();
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    description.as_ptr(),
+                    ptr,
+                    description.len(),
+                );
+                *error_description = ptr;
+            }
+        }
+        return false;
+    }
+
+    let name = unsafe { to_string(name) };
+    match this.profile.borrow().get(&name) {
+        Some(stored) => value.update_from_return(mem, stored).is_ok(),
+        None => false,
+    }
+}
+
+unsafe extern "system" fn write(
+    this: &MockConnection,
+    name: *mut u16,
+    value: &mut TVariant,
+) -> bool {
+    let name = unsafe { to_string(name) };
+    this.profile
+        .borrow_mut()
+        .insert(name, ParamValue::from(&*value));
+    true
+}
+
+unsafe extern "system" fn register_profile_as(
+    this: &MockConnection,
+    name: *mut u16,
+) -> bool {
+    *this.profile_name.borrow_mut() = Some(unsafe { to_string(name) });
+    true
+}
+
+unsafe extern "system" fn set_event_buffer_depth(
+    this: &MockConnection,
+    depth: c_long,
+) -> bool {
+    this.event_buffer_depth.set(depth);
+    true
+}
+
+unsafe extern "system" fn get_event_buffer_depth(
+    this: &MockConnection,
+) -> c_long {
+    this.event_buffer_depth.get()
+}
+
+unsafe extern "system" fn external_event(
+    this: &MockConnection,
+    source: *mut u16,
+    name: *mut u16,
+    data: *mut u16,
+) -> bool {
+    let mut events = this.events.borrow_mut();
+    if events.len() as c_long >= this.event_buffer_depth.get() {
+        return false;
+    }
+    events
+        .push(unsafe { (to_string(source), to_string(name), to_string(data)) });
+    true
+}
+
+unsafe extern "system" fn clean_event_buffer(this: &MockConnection) {
+    this.events.borrow_mut().clear();
+}
+
+unsafe extern "system" fn set_status_line(
+    this: &MockConnection,
+    text: *mut u16,
+) -> bool {
+    this.status_line
+        .borrow_mut()
+        .push(Some(unsafe { to_string(text) }));
+    true
+}
+
+unsafe extern "system" fn reset_status_line(this: &MockConnection) {
+    this.status_line.borrow_mut().push(None);
+}
+
+unsafe extern "system" fn get_interface(
+    this: &MockConnection,
+    interface: Interface,
+) -> *mut c_void {
+    this.interfaces.borrow_mut().push(interface);
+    ptr::null_mut()
+}
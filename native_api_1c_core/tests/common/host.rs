@@ -0,0 +1,143 @@
+use std::ffi::{c_long, c_void};
+
+use native_api_1c_core::{
+    ffi::{
+        connection::Connection, create_component, destroy_component,
+        memory_manager::MemoryManager, provided_types::TVariant,
+    },
+    interface::AddInWrapper,
+};
+
+use super::memory::MockMemoryManager;
+
+/// Same layout as `IInitDoneBase` of 1C platform
+#[repr(C)]
+struct InitDoneVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    init: unsafe extern "system" fn(*mut c_void, &'static Connection) -> bool,
+    set_mem_manager:
+        unsafe extern "system" fn(*mut c_void, &'static MemoryManager) -> bool,
+    get_info: unsafe extern "system" fn(*mut c_void) -> c_long,
+    done: unsafe extern "system" fn(*mut c_void),
+}
+
+/// Same layout as `ILanguageExtenderBase` of 1C platform, methods, that are
+/// not used by tests, are kept as `usize`
+#[repr(C)]
+struct LanguageExtenderVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    register_extension_as: usize,
+    get_n_props: usize,
+    find_prop: usize,
+    get_prop_name: usize,
+    get_prop_val:
+        unsafe extern "system" fn(*mut c_void, c_long, &mut TVariant) -> bool,
+    set_prop_val: usize,
+    is_prop_readable: usize,
+    is_prop_writable: usize,
+    get_n_methods: usize,
+    find_method: usize,
+    get_method_name: usize,
+    get_n_params: usize,
+    get_param_def_value: usize,
+    has_ret_val: usize,
+    call_as_proc: unsafe extern "system" fn(
+        *mut c_void,
+        c_long,
+        *mut TVariant,
+        c_long,
+    ) -> bool,
+    call_as_func: unsafe extern "system" fn(
+        *mut c_void,
+        c_long,
+        &mut TVariant,
+        *mut TVariant,
+        c_long,
+    ) -> bool,
+}
+
+/// Component, created the way 1C platform does it, called only through its
+/// interfaces
+pub struct MockHost {
+    component: *mut c_void,
+}
+
+impl MockHost {
+    pub fn new<T: AddInWrapper>(
+        addin: T,
+        mem: &'static MockMemoryManager,
+    ) -> Self {
+        let mut component = std::ptr::null_mut();
+        unsafe { create_component(&mut component, addin) };
+        let host = Self { component };
+        assert!(unsafe {
+            (host.init_done().set_mem_manager)(host.component, mem.as_mem())
+        });
+        host
+    }
+
+    /// Interface pointer, that 1C platform passes as `this`
+    fn interface(&self, offset: usize) -> *mut c_void {
+        unsafe { self.component.cast::<*const c_void>().add(offset).cast() }
+    }
+
+    fn init_done(&self) -> &InitDoneVTable {
+        unsafe { &**self.component.cast::<*const InitDoneVTable>() }
+    }
+
+    fn lang_extender(&self) -> &LanguageExtenderVTable {
+        unsafe { &**self.interface(1).cast::<*const LanguageExtenderVTable>() }
+    }
+
+    pub fn init(&self, connection: &'static Connection) -> bool {
+        unsafe { (self.init_done().init)(self.component, connection) }
+    }
+
+    pub fn get_prop_val(&self, num: usize, value: &mut TVariant) -> bool {
+        unsafe {
+            (self.lang_extender().get_prop_val)(
+                self.interface(1),
+                num as c_long,
+                value,
+            )
+        }
+    }
+
+    pub fn call_as_proc(&self, method: usize, params: &mut [TVariant]) -> bool {
+        unsafe {
+            (self.lang_extender().call_as_proc)(
+                self.interface(1),
+                method as c_long,
+                params.as_mut_ptr(),
+                params.len() as c_long,
+            )
+        }
+    }
+
+    pub fn call_as_func(
+        &self,
+        method: usize,
+        ret_value: &mut TVariant,
+        params: &mut [TVariant],
+    ) -> bool {
+        unsafe {
+            (self.lang_extender().call_as_func)(
+                self.interface(1),
+                method as c_long,
+                ret_value,
+                params.as_mut_ptr(),
+                params.len() as c_long,
+            )
+        }
+    }
+}
+
+impl Drop for MockHost {
+    fn drop(&mut self) {
+        unsafe { destroy_component(&mut self.component) };
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::{c_ulong, c_void},
+    ptr,
+};
+
+use native_api_1c_core::ffi::memory_manager::MemoryManager;
+
+/// Same layout as `IMemoryManager` of 1C platform
+#[repr(C)]
+struct MockMemoryManagerVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    alloc_memory: unsafe extern "system" fn(
+        &MockMemoryManager,
+        *mut *mut c_void,
+        c_ulong,
+    ) -> bool,
+    free_memory:
+        unsafe extern "system" fn(&MockMemoryManager, *mut *mut c_void),
+}
+
+static VTABLE: MockMemoryManagerVTable = MockMemoryManagerVTable {
+    dtor: 0,
+    #[cfg(target_family = "unix")]
+    dtor2: 0,
+    alloc_memory,
+    free_memory,
+};
+
+/// Tracks live allocations and frees of pointers, that it did not allocate
+/// or already freed
+#[repr(C)]
+pub struct MockMemoryManager {
+    vptr: &'static MockMemoryManagerVTable,
+    /// address to length in `u16`, so that strings are aligned
+    pub live: RefCell<HashMap<usize, usize>>,
+    pub invalid_frees: RefCell<Vec<usize>>,
+    /// all allocations fail
+    pub fail: Cell<bool>,
+    /// number of allocations, that succeed before all others fail
+    pub fail_after: Cell<Option<usize>>,
+}
+
+impl MockMemoryManager {
+    pub fn new() -> Self {
+        Self {
+            vptr: &VTABLE,
+            live: RefCell::default(),
+            invalid_frees: RefCell::default(),
+            fail: Cell::new(false),
+            fail_after: Cell::new(None),
+        }
+    }
+
+    /// Memory manager, that lives as long as 1C platform passes it
+    pub fn leaked() -> &'static Self {
+        Box::leak(Box::new(Self::new()))
+    }
+
+    pub fn as_mem(&self) -> &MemoryManager {
+        unsafe { &*(self as *const Self).cast::<MemoryManager>() }
+    }
+
+    pub fn live_count(&self) -> usize {
+        self.live.borrow().len()
+    }
+
+    pub fn assert_no_invalid_frees(&self) {
+        assert_eq!(*self.invalid_frees.borrow(), Vec::<usize>::new());
+    }
+}
+
+impl Drop for MockMemoryManager {
+    fn drop(&mut self) {
+        for (addr, len) in self.live.get_mut().drain() {
+            drop(unsafe { Vec::from_raw_parts(addr as *mut u16, len, len) });
+        }
+    }
+}
+
+unsafe extern "system" fn alloc_memory(
+    mem: &MockMemoryManager,
+    ptr: *mut *mut c_void,
+    size: c_ulong,
+) -> bool {
+    if mem.fail.get() {
+        return false;
+    }
+    match mem.fail_after.get() {
+        Some(0) => return false,
+        Some(left) => mem.fail_after.set(Some(left - 1)),
+        None => {}
+    }
+    let len = (size as usize).div_ceil(2).max(1);
+    let mut block = vec![0_u16; len];
+    block.shrink_to_fit();
+    let addr = block.leak().as_mut_ptr();
+    mem.live.borrow_mut().insert(addr as usize, len);
+    unsafe { *ptr = addr.cast() };
+    true
+}
+
+unsafe extern "system" fn free_memory(
+    mem: &MockMemoryManager,
+    ptr: *mut *mut c_void,
+) {
+    let addr = unsafe { *ptr } as usize;
+    match mem.live.borrow_mut().remove(&addr) {
+        Some(len) => {
+            drop(unsafe { Vec::from_raw_parts(addr as *mut u16, len, len) })
+        }
+        None => mem.invalid_frees.borrow_mut().push(addr),
+    }
+    unsafe { *ptr = ptr::null_mut() };
+}
@@ -0,0 +1,7 @@
+//! Mocks of 1C platform interfaces, shared by integration tests. Every test
+//! uses only part of them
+#![allow(dead_code)]
+
+pub mod connection;
+pub mod host;
+pub mod memory;
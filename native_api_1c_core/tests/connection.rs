@@ -0,0 +1,141 @@
+mod common;
+
+use std::error::Error;
+
+use native_api_1c_core::{
+    ffi::{
+        AppCapabilities,
+        connection::{Interface, MessageCode},
+        set_platform_capabilities,
+    },
+    interface::{AddInError, ParamValue},
+};
+use rstest::rstest;
+
+use common::{connection::MockConnection, memory::MockMemoryManager};
+
+#[test]
+fn test_register_profile_as() {
+    let connection = MockConnection::new(None);
+
+    assert!(connection.as_connection().register_profile_as("Профиль"));
+    assert_eq!(
+        *connection.profile_name.borrow(),
+        Some("Профиль".to_owned())
+    );
+}
+
+#[rstest]
+#[case::bool(ParamValue::Bool(true))]
+#[case::i32(ParamValue::I32(-42))]
+#[case::f64(ParamValue::F64(1.5))]
+#[case::string(ParamValue::from_str("Значение"))]
+#[case::empty_string(ParamValue::from_str(""))]
+#[case::blob(ParamValue::Blob(vec![1, 2, 3]))]
+fn test_write_read(#[case] value: ParamValue) {
+    let mem = MockMemoryManager::leaked();
+    let connection = MockConnection::new(Some(mem));
+
+    assert!(
+        connection
+            .as_connection()
+            .write(mem.as_mem(), "Key", &value)
+    );
+    // value, passed to 1C platform, is freed after the call
+    assert_eq!(mem.live_count(), 0);
+    assert_eq!(connection.profile.borrow().get("Key"), Some(&value));
+
+    // value, allocated by 1C platform, is freed after it is read
+    let read = connection.as_connection().read(mem.as_mem(), "Key");
+    assert_eq!(read.unwrap(), value);
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+}
+
+#[test]
+fn test_write_allocation_error() {
+    let mem = MockMemoryManager::leaked();
+    let connection = MockConnection::new(Some(mem));
+
+    mem.fail.set(true);
+    let value = ParamValue::from_str("Значение");
+    assert!(
+        !connection
+            .as_connection()
+            .write(mem.as_mem(), "Key", &value)
+    );
+    mem.fail.set(false);
+
+    assert!(connection.profile.borrow().is_empty());
+    assert_eq!(mem.live_count(), 0);
+}
+
+#[rstest]
+#[case::with_description(
+    Some("Значение не найдено"),
+    "Значение не найдено (5)"
+)]
+#[case::without_description(None, "profile read error (5)")]
+fn test_read_error(#[case] description: Option<&str>, #[case] message: &str) {
+    let mem = MockMemoryManager::leaked();
+    let connection = MockConnection::new(Some(mem));
+    *connection.read_error.borrow_mut() =
+        Some((5, description.map(str::to_owned)));
+
+    let error = connection
+        .as_connection()
+        .read(mem.as_mem(), "Key")
+        .unwrap_err();
+    assert_eq!(error.code, 5);
+    assert_eq!(error.description.as_deref(), description);
+    assert_eq!(error.to_string(), message);
+
+    // description, allocated by 1C platform, is freed
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+
+    let error = AddInError::from(error);
+    assert_eq!(error.message(), message);
+    assert_eq!(error.description(), message);
+    assert_eq!(error.code(), MessageCode::Fail);
+    assert_eq!(error.scode(), AddInError::DEFAULT_SCODE);
+    assert_eq!(error.source().unwrap().to_string(), message);
+}
+
+#[test]
+fn test_read_missing() {
+    let mem = MockMemoryManager::leaked();
+    let connection = MockConnection::new(Some(mem));
+
+    let error = connection
+        .as_connection()
+        .read(mem.as_mem(), "Missing")
+        .unwrap_err();
+    assert_eq!(error.code, 0);
+    assert_eq!(error.description, None);
+    assert_eq!(mem.live_count(), 0);
+}
+
+#[test]
+fn test_get_interface_capabilities() {
+    let connection = MockConnection::new(None);
+
+    // platform, that did not call `SetPlatformCapabilities`, passes
+    // `IAddInDefBase` without `GetInterface`, so it is not called
+    set_platform_capabilities(0, AppCapabilities::LATEST);
+    assert_eq!(
+        connection.as_connection().get_interface(Interface::MsgBox),
+        None
+    );
+    assert!(connection.as_connection().msg_box().is_none());
+    assert_eq!(connection.as_connection().platform_info(), None);
+    assert_eq!(connection.as_connection().attach_type(), None);
+    assert!(connection.interfaces.borrow().is_empty());
+
+    set_platform_capabilities(1, AppCapabilities::LATEST);
+    assert_eq!(
+        connection.as_connection().get_interface(Interface::MsgBox),
+        None
+    );
+    assert_eq!(*connection.interfaces.borrow(), [Interface::MsgBox]);
+}
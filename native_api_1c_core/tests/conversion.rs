@@ -0,0 +1,240 @@
+use std::fmt::Debug;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use native_api_1c_core::{
+    ffi::provided_types::Tm,
+    interface::{ConversionError, ParamValue, ParamValues},
+    widestring::{U16Str, U16String, u16str},
+};
+use rstest::rstest;
+
+fn date_time() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 5, 17)
+        .unwrap()
+        .and_hms_opt(12, 30, 0)
+        .unwrap()
+}
+
+fn round_trip<T>(value: T, expected: ParamValue)
+where
+    T: Clone + Debug + PartialEq + Into<ParamValue>,
+    T: TryFrom<ParamValue, Error = ConversionError>,
+    T: for<'a> TryFrom<&'a ParamValue, Error = ConversionError>,
+{
+    let param = value.clone().into();
+    assert_eq!(param, expected);
+    assert_eq!(T::try_from(&param), Ok(value.clone()));
+    assert_eq!(T::try_from(param), Ok(value));
+}
+
+#[test]
+fn test_round_trip() {
+    round_trip(true, ParamValue::Bool(true));
+    round_trip(-5_i8, ParamValue::I32(-5));
+    round_trip(-5_i16, ParamValue::I32(-5));
+    round_trip(-5_i32, ParamValue::I32(-5));
+    round_trip(-5_i64, ParamValue::I64(-5));
+    round_trip(5_u8, ParamValue::I32(5));
+    round_trip(5_u16, ParamValue::I32(5));
+    round_trip(u32::MAX, ParamValue::I64(u32::MAX.into()));
+    round_trip(u64::MAX, ParamValue::U64(u64::MAX));
+    round_trip(0.5_f32, ParamValue::F64(0.5));
+    round_trip(0.5_f64, ParamValue::F64(0.5));
+    round_trip("hello".to_string(), ParamValue::from_str("hello"));
+    round_trip(
+        U16String::from_str("a\0b"),
+        ParamValue::String(U16String::from_str("a\0b")),
+    );
+    round_trip(vec![1_u8, 2, 3], ParamValue::Blob(vec![1, 2, 3]));
+    round_trip(Tm::EMPTY_DATE, ParamValue::Date(Tm::EMPTY_DATE));
+    round_trip(date_time(), ParamValue::Date(date_time().into()));
+}
+
+#[test]
+fn test_from() {
+    assert_eq!(ParamValue::from("hello"), ParamValue::from_str("hello"));
+    assert_eq!(
+        ParamValue::from(&[1_u8, 2][..]),
+        ParamValue::Blob(vec![1, 2])
+    );
+    assert_eq!(ParamValue::from(0.5_f32), ParamValue::F64(0.5));
+
+    let utc = "2024-05-17T12:30:00Z".parse::<DateTime<Utc>>().unwrap();
+    assert_eq!(ParamValue::from(utc).to_date_utc(), Some(utc));
+    assert_eq!(DateTime::<Utc>::try_from(ParamValue::from(utc)), Ok(utc));
+}
+
+#[test]
+fn test_option() {
+    assert_eq!(ParamValue::from(None::<i32>), ParamValue::Empty);
+    assert_eq!(ParamValue::from(Some(5)), ParamValue::I32(5));
+    assert_eq!(ParamValue::from(None::<&str>), ParamValue::Empty);
+
+    assert_eq!(Option::<i32>::try_from(ParamValue::Empty), Ok(None));
+    assert_eq!(Option::<String>::try_from(ParamValue::Null), Ok(None));
+    assert_eq!(Option::<i32>::try_from(ParamValue::I32(5)), Ok(Some(5)));
+    assert_eq!(
+        Option::<i32>::try_from(ParamValue::Bool(true)),
+        Err(ConversionError {
+            expected: "i32",
+            actual: "Bool",
+        })
+    );
+}
+
+#[rstest]
+#[case(ParamValue::I64(300), "u8", "I64")]
+#[case(ParamValue::I32(-1), "u64", "I32")]
+#[case(ParamValue::F64(0.5), "i64", "F64")]
+#[case(ParamValue::Empty, "i32", "Empty")]
+fn test_integer_out_of_range(
+    #[case] value: ParamValue,
+    #[case] expected: &'static str,
+    #[case] actual: &'static str,
+) {
+    let error = match expected {
+        "u8" => u8::try_from(value).map(drop),
+        "u64" => u64::try_from(value).map(drop),
+        "i64" => i64::try_from(value).map(drop),
+        _ => i32::try_from(value).map(drop),
+    };
+    assert_eq!(error, Err(ConversionError { expected, actual }));
+}
+
+#[test]
+fn test_integer_widening() {
+    assert_eq!(i32::try_from(ParamValue::I64(5)), Ok(5));
+    assert_eq!(i64::try_from(ParamValue::U64(5)), Ok(5));
+    assert_eq!(u8::try_from(ParamValue::F64(5.0)), Ok(5));
+}
+
+#[rstest]
+#[case::i32(ParamValue::I32(-5), Some(-5.0))]
+#[case::i64_exact(ParamValue::I64(1 << 53), Some(9007199254740992.0))]
+#[case::i64_inexact(ParamValue::I64((1 << 53) + 1), None)]
+#[case::i64_max(ParamValue::I64(i64::MAX), None)]
+#[case::i64_min(ParamValue::I64(i64::MIN), Some(-9223372036854775808.0))]
+#[case::u64_max(ParamValue::U64(u64::MAX), None)]
+#[case::string(ParamValue::from_str("5"), None)]
+fn test_float_from_integer(
+    #[case] value: ParamValue,
+    #[case] expected: Option<f64>,
+) {
+    assert_eq!(f64::try_from(&value).ok(), expected);
+    assert_eq!(f64::try_from(value).ok(), expected);
+}
+
+#[rstest]
+#[case(ParamValue::F64(0.1), Some(0.1))]
+#[case(ParamValue::I32(5), Some(5.0))]
+#[case(ParamValue::F64(f64::INFINITY), Some(f32::INFINITY))]
+#[case(ParamValue::F64(f32::MAX.into()), Some(f32::MAX))]
+#[case(ParamValue::F64(1e39), None)]
+#[case(ParamValue::F64(-1e39), None)]
+fn test_f32_range(#[case] value: ParamValue, #[case] expected: Option<f32>) {
+    assert_eq!(f32::try_from(value).ok(), expected);
+}
+
+#[test]
+fn test_date_out_of_range() {
+    let value = ParamValue::Date(Tm {
+        year: 10000 - 1900,
+        ..Tm::EMPTY_DATE
+    });
+    assert_eq!(
+        NaiveDateTime::try_from(value),
+        Err(ConversionError {
+            expected: "NaiveDateTime",
+            actual: "Date",
+        })
+    );
+}
+
+#[test]
+fn test_error_message() {
+    let error = String::try_from(ParamValue::F64(1.0)).unwrap_err();
+    assert_eq!(error.to_string(), "expected `String`, got `F64`");
+}
+
+#[test]
+fn test_params_get() {
+    let params = ParamValues::new(vec![
+        ParamValue::I32(5),
+        ParamValue::from_str("hello"),
+        ParamValue::Empty,
+    ]);
+
+    assert_eq!(params.get::<i32>(0).unwrap(), 5);
+    assert_eq!(params.get::<f64>(0).unwrap(), 5.0);
+    assert_eq!(params.get::<String>(1).unwrap(), "hello");
+    assert_eq!(params.get::<Option<String>>(2).unwrap(), None);
+
+    let error = params.get::<bool>(1).unwrap_err();
+    assert_eq!(
+        error.description(),
+        "invalid value of parameter #2: expected `bool`, got `String`"
+    );
+    assert_eq!(
+        params.get::<i32>(3).unwrap_err().description(),
+        "invalid value of parameter #4"
+    );
+}
+
+#[test]
+fn test_params_get_borrowed() {
+    let params = ParamValues::new(vec![
+        ParamValue::from_str("hello"),
+        ParamValue::Blob(vec![1, 2, 3]),
+        ParamValue::Empty,
+    ]);
+
+    let string: &U16Str = params.get(0).unwrap();
+    assert_eq!(string, u16str!("hello"));
+    let blob: &[u8] = params.get(1).unwrap();
+    assert_eq!(blob, [1, 2, 3]);
+    // parameters are borrowed, not copied
+    match (&params[0], &params[1]) {
+        (ParamValue::String(s), ParamValue::Blob(b)) => {
+            assert_eq!(string.as_ptr(), s.as_ptr());
+            assert_eq!(blob.as_ptr(), b.as_ptr());
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(params.get::<Option<&[u8]>>(2).unwrap(), None);
+
+    assert_eq!(
+        params.get::<&[u8]>(0).unwrap_err().description(),
+        "invalid value of parameter #1: expected `&[u8]`, got `String`"
+    );
+}
+
+#[test]
+fn test_params_set() {
+    let mut params = ParamValues::new(vec![ParamValue::Empty; 2]);
+
+    params.set(0, 5).unwrap();
+    params.set(1, "hello").unwrap();
+    assert_eq!(params[0], ParamValue::I32(5));
+    assert_eq!(params[1], ParamValue::from_str("hello"));
+    assert!(params.set(2, true).is_err());
+}
+
+#[test]
+fn test_params_modified() {
+    let mut params = ParamValues::new(vec![ParamValue::Empty; 4]);
+    assert_eq!(params.iter_modified().count(), 0);
+
+    let _ = params.get::<Option<i32>>(0);
+    let _ = &params[1];
+    params.set(2, 5).unwrap();
+    params[3].set_str("out");
+
+    assert!(!params.is_modified(0));
+    assert!(!params.is_modified(1));
+    assert!(!params.is_modified(4));
+    assert_eq!(
+        params.iter_modified().collect::<Vec<_>>(),
+        vec![(2, &ParamValue::I32(5)), (3, &ParamValue::from_str("out"))]
+    );
+    assert!(params.clone().is_modified(3));
+}
@@ -0,0 +1,249 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use native_api_1c_core::{
+    ffi::provided_types::{DateRangeError, Tm},
+    interface::ParamValue,
+};
+use rstest::rstest;
+
+fn tm(year: i32, mon: i32, mday: i32, hour: i32, min: i32, sec: i32) -> Tm {
+    Tm {
+        sec,
+        min,
+        hour,
+        mday,
+        mon: mon - 1,
+        year: year - 1900,
+        ..Tm::EMPTY_DATE
+    }
+}
+
+fn date_time(year: i32, month: u32, day: u32, sec: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap()
+        .and_hms_opt(0, 0, sec)
+        .unwrap()
+}
+
+#[test]
+fn test_empty_date() {
+    assert!(Tm::EMPTY_DATE.is_empty_date());
+    assert_eq!(Tm::EMPTY_DATE.to_optional_date_time(), Ok(None));
+    assert_eq!(Tm::from_optional_date_time(None), Tm::EMPTY_DATE);
+    assert_eq!(
+        NaiveDateTime::try_from(Tm::EMPTY_DATE),
+        Ok(date_time(1, 1, 1, 0))
+    );
+}
+
+#[test]
+fn test_empty_date_ignores_derived_fields() {
+    let value = Tm {
+        wday: 0,
+        yday: 5,
+        isdst: 1,
+        ..Tm::EMPTY_DATE
+    };
+    assert!(value.is_empty_date());
+}
+
+#[rstest]
+#[case(tm(1, 1, 1, 0, 0, 1), date_time(1, 1, 1, 1))]
+#[case(tm(1899, 12, 31, 0, 0, 0), date_time(1899, 12, 31, 0))]
+#[case(tm(1970, 1, 1, 0, 0, 0), date_time(1970, 1, 1, 0))]
+#[case(tm(2024, 2, 29, 0, 0, 0), date_time(2024, 2, 29, 0))]
+#[case(tm(9999, 12, 31, 0, 0, 0), date_time(9999, 12, 31, 0))]
+// leap second is clamped
+#[case(tm(2016, 12, 31, 0, 0, 60), date_time(2016, 12, 31, 59))]
+fn test_date_in_range(#[case] value: Tm, #[case] expected: NaiveDateTime) {
+    assert_eq!(NaiveDateTime::try_from(&value), Ok(expected));
+    assert_eq!(value.to_optional_date_time(), Ok(Some(expected)));
+}
+
+#[rstest]
+#[case(tm(0, 12, 31, 0, 0, 0))]
+#[case(tm(10000, 1, 1, 0, 0, 0))]
+#[case(tm(-1, 1, 1, 0, 0, 0))]
+#[case(tm(2023, 2, 29, 0, 0, 0))]
+#[case(tm(2024, 13, 1, 0, 0, 0))]
+#[case(tm(2024, 0, 1, 0, 0, 0))]
+#[case(tm(2024, 1, 0, 0, 0, 0))]
+#[case(tm(2024, 1, 1, 24, 0, 0))]
+#[case(tm(2024, 1, 1, 0, -1, 0))]
+#[case(tm(i32::MAX, 1, 1, 0, 0, 0))]
+fn test_date_out_of_range(#[case] value: Tm) {
+    assert_eq!(
+        NaiveDateTime::try_from(&value),
+        Err(DateRangeError::from(&value))
+    );
+    assert!(value.to_optional_date_time().is_err());
+}
+
+#[test]
+fn test_date_range_error_message() {
+    let error = DateRangeError::from(&tm(10000, 1, 2, 3, 4, 5));
+    assert_eq!(error.year, 10000);
+    assert_eq!(error.month, 1);
+    assert_eq!(
+        error.to_string(),
+        "date 10000-01-02 03:04:05 is invalid or out of range"
+    );
+}
+
+#[rstest]
+#[case(date_time(1, 1, 1, 1))]
+#[case(date_time(1900, 1, 1, 0))]
+#[case(date_time(9999, 12, 31, 59))]
+fn test_date_round_trip(#[case] value: NaiveDateTime) {
+    let tm = Tm::from(value);
+    assert!(!tm.is_empty_date());
+    assert_eq!(NaiveDateTime::try_from(tm), Ok(value));
+}
+
+#[test]
+fn test_nullable_date_param() {
+    let empty = ParamValue::from_nullable_date(None);
+    assert_eq!(empty, ParamValue::Date(Tm::EMPTY_DATE));
+    assert_eq!(empty.to_nullable_date(), Some(None));
+
+    let value = date_time(2024, 5, 17, 30);
+    let param = ParamValue::from_nullable_date(Some(value));
+    assert_eq!(param.to_nullable_date(), Some(Some(value)));
+
+    let out_of_range = ParamValue::Date(tm(10000, 1, 1, 0, 0, 0));
+    assert_eq!(out_of_range.to_nullable_date(), None);
+    assert_eq!(ParamValue::I32(1).to_nullable_date(), None);
+}
+
+#[rstest]
+#[case("0001-01-02T00:00:00Z")]
+#[case("1970-01-01T00:00:00Z")]
+#[case("2024-03-31T01:30:00Z")]
+#[case("2024-10-27T00:30:00Z")]
+#[case("9999-12-30T23:59:59Z")]
+fn test_date_utc_round_trip(#[case] value: &str) {
+    let value = value.parse::<DateTime<Utc>>().unwrap();
+    let tm = Tm::from(value);
+    assert_eq!(DateTime::<Utc>::try_from(tm), Ok(value));
+    assert_eq!(ParamValue::from_date_utc(value).to_date_utc(), Some(value));
+}
+
+#[test]
+fn test_date_tz_keeps_instant() {
+    let value = "2024-05-17T12:00:00+05:00"
+        .parse::<DateTime<FixedOffset>>()
+        .unwrap();
+    let converted = DateTime::<FixedOffset>::try_from(Tm::from(value)).unwrap();
+    assert_eq!(converted, value);
+    assert_eq!(converted.to_utc(), value.to_utc());
+}
+
+#[test]
+fn test_date_tz_out_of_range() {
+    let value = tm(10000, 1, 1, 0, 0, 0);
+    assert_eq!(
+        DateTime::<Utc>::try_from(value),
+        Err(DateRangeError::from(&value))
+    );
+    assert_eq!(ParamValue::Date(value).to_date_tz(), None);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn test_unix_layout() {
+    use std::{
+        ffi::{c_int, c_long},
+        mem::{offset_of, size_of},
+    };
+
+    assert_eq!(offset_of!(Tm, isdst), size_of::<[c_int; 8]>());
+    assert_eq!(offset_of!(Tm, gmtoff) % size_of::<c_long>(), 0);
+    assert!(
+        offset_of!(Tm, zone) >= offset_of!(Tm, gmtoff) + size_of::<c_long>()
+    );
+
+    let value = Tm {
+        gmtoff: 3 * 60 * 60,
+        ..tm(2024, 5, 17, 0, 0, 0)
+    };
+    assert_eq!(
+        NaiveDateTime::try_from(value),
+        Ok(date_time(2024, 5, 17, 0))
+    );
+    assert_eq!(Tm::EMPTY_DATE.gmtoff, 0);
+    assert!(Tm::EMPTY_DATE.zone.is_null());
+    assert!(Tm::default().zone.is_null());
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn test_unix_offset() {
+    let value = Tm {
+        gmtoff: 3 * 60 * 60,
+        ..tm(2024, 5, 17, 0, 0, 0)
+    };
+    assert_eq!(value.utc_offset(), FixedOffset::east_opt(3 * 60 * 60));
+    assert_eq!(
+        value.to_date_time_tz(),
+        Ok("2024-05-17T00:00:00+03:00".parse().unwrap())
+    );
+    assert_eq!(
+        DateTime::<Utc>::try_from(value),
+        Ok("2024-05-16T21:00:00Z".parse().unwrap())
+    );
+
+    // zero offset is known, when zone is set
+    let value = Tm {
+        zone: c"UTC".as_ptr(),
+        ..tm(2024, 5, 17, 0, 0, 0)
+    };
+    assert_eq!(value.utc_offset(), FixedOffset::east_opt(0));
+    assert_eq!(tm(2024, 5, 17, 0, 0, 0).utc_offset(), None);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn test_unix_offset_from_date_time() {
+    let value = "2024-05-17T12:00:00+05:00"
+        .parse::<DateTime<FixedOffset>>()
+        .unwrap();
+    let tm = Tm::from(value);
+    assert_eq!(
+        tm.utc_offset()
+            .map(|offset| offset.local_minus_utc())
+            .unwrap_or(0),
+        tm.gmtoff as i32
+    );
+    assert_eq!(
+        tm.to_date_time_tz().map(|date| date.to_utc()),
+        Ok(value.to_utc())
+    );
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn test_unix_eq_ignores_zone() {
+    let zone = String::from("MSK\0");
+    let value = Tm {
+        gmtoff: 3 * 60 * 60,
+        zone: c"MSK".as_ptr(),
+        ..tm(2024, 5, 17, 0, 0, 0)
+    };
+    let copy = Tm {
+        zone: zone.as_ptr().cast(),
+        ..value
+    };
+    assert_eq!(value, copy);
+    assert_ne!(value, Tm { gmtoff: 0, ..copy });
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn test_windows_layout() {
+    use std::{ffi::c_int, mem::size_of};
+
+    assert_eq!(size_of::<Tm>(), size_of::<[c_int; 9]>());
+    assert_eq!(
+        NaiveDateTime::try_from(tm(2024, 5, 17, 0, 0, 0)),
+        Ok(date_time(2024, 5, 17, 0))
+    );
+}
@@ -0,0 +1,52 @@
+use native_api_1c_core::{
+    interface::{DecimalError, ParamValue},
+    rust_decimal::Decimal,
+};
+use rstest::rstest;
+
+fn overflow(value: &str) -> Result<Decimal, DecimalError> {
+    Err(DecimalError::Overflow(value.to_owned()))
+}
+
+fn precision_loss(value: &str) -> Result<Decimal, DecimalError> {
+    Err(DecimalError::PrecisionLoss(value.to_owned()))
+}
+
+fn invalid(value: &str) -> Result<Decimal, DecimalError> {
+    Err(DecimalError::Invalid(value.to_owned()))
+}
+
+#[rstest]
+#[case("-1234.56", Ok(Decimal::new(-123456, 2)))]
+#[case(" 1_000.5 ", Ok(Decimal::new(10005, 1)))]
+#[case(".5", Ok(Decimal::new(5, 1)))]
+#[case("1.5e3", Ok(Decimal::new(1500, 0)))]
+#[case::too_many_digits(
+    "1000000000000000000000000000000",
+    overflow("1000000000000000000000000000000")
+)]
+#[case::too_many_digits_negative(
+    "-99999999999999999999999999999.5",
+    overflow("-99999999999999999999999999999.5")
+)]
+#[case::large_exponent("1e40", overflow("1e40"))]
+#[case::too_many_fractional_digits(
+    "0.00000000000000000000000000001",
+    precision_loss("0.00000000000000000000000000001")
+)]
+#[case::small_exponent("1e-40", precision_loss("1e-40"))]
+#[case::comma("1234,56", invalid("1234,56"))]
+#[case::empty("", invalid(""))]
+#[case::sign_only("-", invalid("-"))]
+#[case::two_points("1.2.3", invalid("1.2.3"))]
+#[case::letters("abc", invalid("abc"))]
+#[case::long_letters(
+    "1000000000000000000000000000000x",
+    invalid("1000000000000000000000000000000x")
+)]
+fn test_try_to_decimal(
+    #[case] value: &str,
+    #[case] expected: Result<Decimal, DecimalError>,
+) {
+    assert_eq!(ParamValue::from_str(value).try_to_decimal(), expected);
+}
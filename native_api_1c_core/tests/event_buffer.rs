@@ -0,0 +1,185 @@
+mod common;
+
+use native_api_1c_core::interface::{
+    EventBuffer, EventBufferConfig, EventBufferStats,
+};
+use rstest::rstest;
+
+use common::connection::{Event, MockConnection};
+
+fn event_buffer(
+    depth: i32,
+    config: EventBufferConfig,
+) -> (&'static MockConnection, EventBuffer) {
+    let connection = MockConnection::leaked(None);
+    connection.event_buffer_depth.set(depth.into());
+    (
+        connection,
+        EventBuffer::new(connection.as_connection(), config),
+    )
+}
+
+fn event(data: &str) -> Event {
+    ("Source".to_owned(), "Event".to_owned(), data.to_owned())
+}
+
+fn stats(sent: u64, queued: usize, dropped: u64) -> EventBufferStats {
+    EventBufferStats {
+        sent,
+        queued,
+        dropped,
+    }
+}
+
+#[rstest]
+#[case::increased(1, Some(8), 8)]
+#[case::not_decreased(16, Some(8), 16)]
+#[case::not_configured(4, None, 4)]
+fn test_new_sets_depth(
+    #[case] platform_depth: i32,
+    #[case] depth: Option<i32>,
+    #[case] expected: i32,
+) {
+    let (connection, _buffer) = event_buffer(
+        platform_depth,
+        EventBufferConfig {
+            depth: depth.map(Into::into),
+            ..Default::default()
+        },
+    );
+    assert_eq!(connection.event_buffer_depth.get(), expected.into());
+}
+
+#[test]
+fn test_send_accepted() {
+    let (connection, buffer) = event_buffer(2, EventBufferConfig::default());
+
+    assert!(buffer.send("Source", "Event", "1"));
+    assert!(buffer.send("Source", "Event", "2"));
+    assert_eq!(buffer.stats(), stats(2, 0, 0));
+    assert_eq!(connection.process_events(), [event("1"), event("2")]);
+}
+
+#[test]
+fn test_overflow_is_queued_in_order() {
+    let (connection, buffer) = event_buffer(1, EventBufferConfig::default());
+
+    assert!(buffer.send("Source", "Event", "1"));
+    assert!(!buffer.send("Source", "Event", "2"));
+    assert!(!buffer.send("Source", "Event", "3"));
+    assert_eq!(buffer.stats(), stats(1, 2, 0));
+    assert_eq!(connection.process_events(), [event("1")]);
+
+    // queued events are delivered before the new one, one per free slot
+    assert!(!buffer.send("Source", "Event", "4"));
+    assert_eq!(buffer.stats(), stats(2, 2, 0));
+    assert_eq!(connection.process_events(), [event("2")]);
+
+    assert_eq!(buffer.flush(), 1);
+    assert_eq!(connection.process_events(), [event("3")]);
+    assert_eq!(buffer.flush(), 1);
+    assert_eq!(connection.process_events(), [event("4")]);
+    assert_eq!(buffer.flush(), 0);
+    assert_eq!(buffer.stats(), stats(4, 0, 0));
+}
+
+#[test]
+fn test_retry_after_rejected() {
+    let (connection, buffer) = event_buffer(1, EventBufferConfig::default());
+
+    assert!(buffer.send("Source", "Event", "1"));
+    assert!(!buffer.send("Source", "Event", "2"));
+    assert_eq!(buffer.flush(), 0);
+    assert_eq!(buffer.stats(), stats(1, 1, 0));
+
+    // queued event takes the free slot, so the new one is queued too
+    connection.process_events();
+    assert!(!buffer.send("Source", "Event", "3"));
+    assert_eq!(buffer.stats(), stats(2, 1, 0));
+    assert_eq!(connection.process_events(), [event("2")]);
+    assert_eq!(buffer.flush(), 1);
+    assert_eq!(connection.process_events(), [event("3")]);
+    assert_eq!(buffer.stats(), stats(3, 0, 0));
+}
+
+#[rstest]
+#[case::oldest_dropped(2, stats(1, 2, 2), &["4", "5"])]
+#[case::no_queue(0, stats(1, 0, 4), &[])]
+fn test_overflow_capacity(
+    #[case] overflow_capacity: usize,
+    #[case] expected: EventBufferStats,
+    #[case] queued: &[&str],
+) {
+    let (connection, buffer) = event_buffer(
+        1,
+        EventBufferConfig {
+            overflow_capacity,
+            ..Default::default()
+        },
+    );
+
+    for data in ["1", "2", "3", "4", "5"] {
+        buffer.send("Source", "Event", data);
+    }
+    assert_eq!(buffer.stats(), expected);
+    assert_eq!(connection.process_events(), [event("1")]);
+
+    let mut delivered = Vec::new();
+    while buffer.flush() > 0 {
+        delivered.extend(connection.process_events());
+    }
+    let queued: Vec<_> = queued.iter().map(|data| event(data)).collect();
+    assert_eq!(delivered, queued);
+}
+
+#[rstest]
+#[case::doubled(1, 8, &[1, 2, 4, 8])]
+#[case::capped(3, 10, &[3, 6, 10])]
+#[case::at_least_one_more(0, 2, &[0, 1, 2])]
+fn test_grow_depth(
+    #[case] depth: i32,
+    #[case] max_depth: i32,
+    #[case] depths: &[i32],
+) {
+    let (connection, buffer) = event_buffer(
+        depth,
+        EventBufferConfig {
+            max_depth: Some(max_depth.into()),
+            ..Default::default()
+        },
+    );
+
+    // every event, rejected by a full platform buffer, grows it once
+    let mut observed = vec![connection.event_buffer_depth.get()];
+    let mut sent = 0;
+    while sent < max_depth {
+        assert!(buffer.send("Source", "Event", &sent.to_string()));
+        sent += 1;
+        let depth = connection.event_buffer_depth.get();
+        if observed.last() != Some(&depth) {
+            observed.push(depth);
+        }
+    }
+    let depths: Vec<_> = depths.iter().map(|&depth| depth.into()).collect();
+    assert_eq!(observed, depths);
+
+    // at max depth events are queued
+    assert!(!buffer.send("Source", "Event", "last"));
+    assert_eq!(connection.event_buffer_depth.get(), max_depth.into());
+    assert_eq!(buffer.stats(), stats(sent as u64, 1, 0));
+}
+
+#[test]
+fn test_clean() {
+    let (connection, buffer) = event_buffer(1, EventBufferConfig::default());
+
+    assert!(buffer.send("Source", "Event", "1"));
+    assert!(!buffer.send("Source", "Event", "2"));
+    buffer.clean();
+
+    // discarded events are not counted as dropped
+    assert_eq!(buffer.stats(), stats(1, 0, 0));
+    assert!(connection.process_events().is_empty());
+    assert!(buffer.send("Source", "Event", "3"));
+    assert_eq!(connection.process_events(), [event("3")]);
+}
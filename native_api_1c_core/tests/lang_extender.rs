@@ -0,0 +1,254 @@
+mod common;
+
+use native_api_1c_core::{
+    ffi::{
+        api_version::{ApiVersion, PStrEncoding},
+        connection::Connection,
+        provided_types::{TVariant, VariantType},
+    },
+    interface::{AddInWrapper, AddInWrapperResult, ParamValue, ParamValues},
+    widestring::{U16CStr, U16String, u16cstr},
+};
+use rstest::rstest;
+
+use common::{host::MockHost, memory::MockMemoryManager};
+
+/// AddIn with one function, that writes `out` to its first parameter and
+/// returns `result`, or the value of the first parameter, if `result` is not
+/// set
+struct TestAddIn {
+    out: ParamValue,
+    result: Option<ParamValue>,
+    api_version: ApiVersion,
+    pstr_encoding: PStrEncoding,
+}
+
+impl TestAddIn {
+    fn new(out: ParamValue, result: Option<ParamValue>) -> Self {
+        Self {
+            out,
+            result,
+            api_version: ApiVersion::V2,
+            pstr_encoding: PStrEncoding::Utf8,
+        }
+    }
+}
+
+impl AddInWrapper for TestAddIn {
+    fn init(&mut self, _interface: &'static Connection) -> bool {
+        true
+    }
+
+    fn get_info(&self) -> u16 {
+        self.api_version.info()
+    }
+
+    fn pstr_encoding(&self) -> PStrEncoding {
+        self.pstr_encoding
+    }
+
+    fn done(&mut self) {}
+
+    fn register_extension_as(&mut self) -> &U16CStr {
+        u16cstr!("TestAddIn")
+    }
+
+    fn get_n_props(&self) -> usize {
+        0
+    }
+
+    fn find_prop(&self, _name: &U16CStr) -> Option<usize> {
+        None
+    }
+
+    fn get_prop_name(&self, _num: usize, _alias: usize) -> Option<&U16CStr> {
+        None
+    }
+
+    fn get_prop_val(&self, _num: usize) -> AddInWrapperResult<ParamValue> {
+        Ok(self.out.clone())
+    }
+
+    fn set_prop_val(
+        &mut self,
+        _num: usize,
+        _val: ParamValue,
+    ) -> AddInWrapperResult<()> {
+        Ok(())
+    }
+
+    fn is_prop_readable(&self, _num: usize) -> bool {
+        true
+    }
+
+    fn is_prop_writable(&self, _num: usize) -> bool {
+        false
+    }
+
+    fn get_n_methods(&self) -> usize {
+        1
+    }
+
+    fn find_method(&self, _name: &U16CStr) -> Option<usize> {
+        Some(0)
+    }
+
+    fn get_method_name(&self, _num: usize, _alias: usize) -> Option<&U16CStr> {
+        Some(u16cstr!("Function"))
+    }
+
+    fn get_n_params(&self, _num: usize) -> usize {
+        1
+    }
+
+    fn get_param_def_value(
+        &self,
+        _method_num: usize,
+        _param_num: usize,
+    ) -> Option<ParamValue> {
+        None
+    }
+
+    fn has_ret_val(&self, _method_num: usize) -> bool {
+        true
+    }
+
+    fn call_as_proc(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<()> {
+        self.call_as_func(method_num, params).map(|_| ())
+    }
+
+    fn call_as_func(
+        &mut self,
+        _method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<ParamValue> {
+        let param = std::mem::replace(&mut params[0], self.out.clone());
+        Ok(self.result.clone().unwrap_or(param))
+    }
+
+    fn set_locale(&mut self, _loc: &U16CStr) {}
+
+    fn set_user_interface_language_code(&mut self, _lang: &U16CStr) {}
+}
+
+fn call_as_func(
+    mem: &MockMemoryManager,
+    host: &MockHost,
+    param: &ParamValue,
+) -> (bool, TVariant, TVariant) {
+    let mut ret_value = TVariant::default();
+    let mut params = [TVariant::default()];
+    params[0].update_from_return(mem.as_mem(), param).unwrap();
+    let call_result = host.call_as_func(0, &mut ret_value, &mut params);
+    let [param] = params;
+    (call_result, ret_value, param)
+}
+
+#[test]
+fn test_call_as_func() {
+    let mem = MockMemoryManager::leaked();
+    let host = MockHost::new(
+        TestAddIn::new(
+            ParamValue::from_str("out"),
+            Some(ParamValue::Blob(vec![1, 2, 3])),
+        ),
+        mem,
+    );
+
+    let (call_result, mut ret_value, mut param) =
+        call_as_func(mem, &host, &ParamValue::from_str("in"));
+    assert!(call_result);
+    assert_eq!(
+        ParamValue::from(&ret_value),
+        ParamValue::Blob(vec![1, 2, 3])
+    );
+    assert_eq!(ParamValue::from(&param), ParamValue::from_str("out"));
+
+    // 1C platform frees values, passed to it
+    unsafe { ret_value.free_data(mem.as_mem()) };
+    unsafe { param.free_data(mem.as_mem()) };
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+}
+
+#[rstest]
+#[case::out_param(0)]
+#[case::return_value(1)]
+fn test_call_as_func_allocation_error(#[case] allocations: usize) {
+    let mem = MockMemoryManager::leaked();
+    let host = MockHost::new(
+        TestAddIn::new(
+            ParamValue::from_str("out"),
+            Some(ParamValue::from_str("result")),
+        ),
+        mem,
+    );
+
+    let mut ret_value = TVariant::default();
+    let mut params = [TVariant::default()];
+    params[0].update_to_i32(1);
+    mem.fail_after.set(Some(allocations));
+    assert!(!host.call_as_func(0, &mut ret_value, &mut params));
+    mem.fail_after.set(None);
+
+    // nothing is passed in return value of failed call, so it is not freed
+    // by 1C platform and must not be allocated
+    assert_eq!(ParamValue::from(&ret_value), ParamValue::Empty);
+    let [mut param] = params;
+    unsafe { param.free_data(mem.as_mem()) };
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+}
+
+#[rstest]
+#[case(ApiVersion::V1, PStrEncoding::Utf8, VariantType::PStr)]
+#[case(ApiVersion::V1, PStrEncoding::Windows1251, VariantType::PStr)]
+#[case(ApiVersion::V2, PStrEncoding::Windows1251, VariantType::WStr)]
+fn test_call_as_func_strings(
+    #[case] api_version: ApiVersion,
+    #[case] pstr_encoding: PStrEncoding,
+    #[case] vt: VariantType,
+) {
+    let mem = MockMemoryManager::leaked();
+    let host = MockHost::new(
+        TestAddIn {
+            api_version,
+            pstr_encoding,
+            ..TestAddIn::new(ParamValue::from_str("Ёлка"), None)
+        },
+        mem,
+    );
+    let decode = |variant: &TVariant| match variant.as_pstr() {
+        Some(bytes) => pstr_encoding.decode(bytes),
+        None => ParamValue::from(variant).into_str().unwrap().into(),
+    };
+
+    // 1.0 host passes `PStr` in its encoding, it is decoded in any version
+    let mut ret_value = TVariant::default();
+    let mut params = [TVariant::default()];
+    let input = pstr_encoding.encode(&U16String::from_str("Привет"));
+    unsafe { params[0].update_to_pstr(mem.as_mem(), &input) }.unwrap();
+    assert!(host.call_as_func(0, &mut ret_value, &mut params));
+
+    assert_eq!(ret_value.vt, vt);
+    assert_eq!(decode(&ret_value), U16String::from_str("Привет"));
+    let [mut param] = params;
+    assert_eq!(param.vt, vt);
+    assert_eq!(decode(&param), U16String::from_str("Ёлка"));
+
+    // properties are written the same way
+    let mut prop_value = TVariant::default();
+    assert!(host.get_prop_val(0, &mut prop_value));
+    assert_eq!(prop_value.vt, vt);
+    assert_eq!(decode(&prop_value), U16String::from_str("Ёлка"));
+
+    unsafe { ret_value.free_data(mem.as_mem()) };
+    unsafe { param.free_data(mem.as_mem()) };
+    unsafe { prop_value.free_data(mem.as_mem()) };
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use std::{
+    panic,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use native_api_1c_core::ffi::{
+    connection::MessageCode,
+    panic_guard::{clear_panic_hook, guard, set_panic_hook},
+};
+use rstest::rstest;
+
+use common::connection::{MockConnection, ReportedError};
+
+/// What the hook received, payload is checked to be the original one
+#[derive(Debug, PartialEq, Eq)]
+struct Report {
+    method: String,
+    message: String,
+    payload: Option<String>,
+    has_backtrace: bool,
+}
+
+static REPORTS: Mutex<Vec<Report>> = Mutex::new(Vec::new());
+
+/// Panic hook is process-wide, so tests using it must not run in parallel
+static HOOK_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_hook() -> MutexGuard<'static, ()> {
+    let lock = HOOK_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    REPORTS.lock().unwrap().clear();
+    set_panic_hook(|report| {
+        let payload = report
+            .payload
+            .downcast_ref::<&str>()
+            .map(|payload| payload.to_string())
+            .or_else(|| report.payload.downcast_ref::<String>().cloned());
+        REPORTS.lock().unwrap().push(Report {
+            method: report.method.to_owned(),
+            message: report.message.to_owned(),
+            payload,
+            has_backtrace: report.backtrace.is_some(),
+        });
+    });
+    lock
+}
+
+fn reports() -> Vec<Report> {
+    std::mem::take(&mut REPORTS.lock().unwrap())
+}
+
+#[test]
+fn test_guard_returns_result() {
+    let _lock = lock_hook();
+    let connection = MockConnection::new(None);
+
+    let result = guard(Some(connection.as_connection()), "GetNProps", -1, || 3);
+    assert_eq!(result, 3);
+    assert!(reports().is_empty());
+    assert!(connection.errors.borrow().is_empty());
+}
+
+#[rstest]
+#[case::str(|| panic!("static message"), "static message")]
+#[case::string(|| panic!("formatted {}", 42), "formatted 42")]
+fn test_guard_catches_panic(#[case] f: fn() -> bool, #[case] message: &str) {
+    let _lock = lock_hook();
+    let connection = MockConnection::new(None);
+
+    let result =
+        guard(Some(connection.as_connection()), "CallAsProc", false, f);
+    assert!(!result);
+    assert_eq!(
+        reports(),
+        [Report {
+            method: "CallAsProc".to_owned(),
+            message: message.to_owned(),
+            payload: Some(message.to_owned()),
+            has_backtrace: true,
+        }]
+    );
+    assert_eq!(
+        connection.errors.take(),
+        [ReportedError {
+            code: MessageCode::Fail as u16,
+            source: "CallAsProc".to_owned(),
+            description: format!("panic in `CallAsProc`: {message}"),
+            scode: 1,
+        }]
+    );
+}
+
+#[test]
+fn test_guard_non_string_payload() {
+    let _lock = lock_hook();
+    let connection = MockConnection::new(None);
+
+    let result = guard(Some(connection.as_connection()), "Init", false, || {
+        panic::panic_any(42)
+    });
+    assert!(!result);
+    let [report] = reports().try_into().unwrap();
+    assert_eq!(report.message, "unknown panic");
+    assert_eq!(report.payload, None);
+    assert_eq!(
+        connection.errors.take()[0].description,
+        "panic in `Init`: unknown panic"
+    );
+}
+
+#[test]
+fn test_guard_without_connection() {
+    let _lock = lock_hook();
+
+    let result = guard(None, "GetClassObject", 0, || panic!("no connection"));
+    assert_eq!(result, 0);
+    let [report] = reports().try_into().unwrap();
+    assert_eq!(report.method, "GetClassObject");
+    assert_eq!(report.message, "no connection");
+}
+
+#[test]
+fn test_guard_panicking_hook() {
+    let _lock = lock_hook();
+    set_panic_hook(|_report| panic!("hook failed"));
+    let connection = MockConnection::new(None);
+
+    // panic in the hook does not prevent reporting to 1C platform
+    guard(Some(connection.as_connection()), "Done", (), || {
+        panic!("method failed")
+    });
+    assert_eq!(
+        connection.errors.take()[0].description,
+        "panic in `Done`: method failed"
+    );
+}
+
+#[test]
+fn test_guard_cleared_hook() {
+    let _lock = lock_hook();
+    clear_panic_hook();
+
+    guard(None, "SetLocale", (), || panic!("not reported"));
+    assert!(reports().is_empty());
+}
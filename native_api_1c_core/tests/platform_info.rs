@@ -0,0 +1,114 @@
+use std::ffi::c_int;
+
+use native_api_1c_core::{
+    ffi::platform_info::{AppInfo, AppType, PlatformInfo},
+    widestring::{U16CStr, u16cstr},
+};
+use rstest::rstest;
+
+/// Same layout as `IPlatformInfo::AppInfo` of 1C platform
+#[repr(C)]
+struct MockAppInfo {
+    app_version: *const u16,
+    user_agent_information: *const u16,
+    application: c_int,
+}
+
+/// Same layout as `IPlatformInfo` of 1C platform
+#[repr(C)]
+struct MockPlatformInfoVTable {
+    get_platform_info:
+        unsafe extern "system" fn(&MockPlatformInfo) -> *const MockAppInfo,
+}
+
+static VTABLE: MockPlatformInfoVTable =
+    MockPlatformInfoVTable { get_platform_info };
+
+#[repr(C)]
+struct MockPlatformInfo {
+    vptr: &'static MockPlatformInfoVTable,
+    app_info: Option<MockAppInfo>,
+}
+
+impl MockPlatformInfo {
+    fn new(app_info: Option<MockAppInfo>) -> Self {
+        Self {
+            vptr: &VTABLE,
+            app_info,
+        }
+    }
+
+    fn as_platform_info(&self) -> &PlatformInfo {
+        unsafe { &*(self as *const Self).cast::<PlatformInfo>() }
+    }
+}
+
+unsafe extern "system" fn get_platform_info(
+    this: &MockPlatformInfo,
+) -> *const MockAppInfo {
+    match &this.app_info {
+        Some(app_info) => app_info,
+        None => std::ptr::null(),
+    }
+}
+
+fn app_info(
+    app_version: &'static U16CStr,
+    user_agent: Option<&'static U16CStr>,
+    application: c_int,
+) -> MockAppInfo {
+    MockAppInfo {
+        app_version: app_version.as_ptr(),
+        user_agent_information: user_agent
+            .map_or(std::ptr::null(), U16CStr::as_ptr),
+        application,
+    }
+}
+
+#[rstest]
+#[case(
+    app_info(u16cstr!("8.3.24.1548"), Some(u16cstr!("Mozilla/5.0")), 2),
+    "8.3.24.1548",
+    AppType::WebClient,
+    "Mozilla/5.0"
+)]
+#[case(
+    app_info(u16cstr!("8.3.24.1548"), None, 3),
+    "8.3.24.1548",
+    AppType::Server,
+    ""
+)]
+#[case(
+    app_info(u16cstr!("8.3.10.2639"), Some(u16cstr!("")), 6),
+    "8.3.10.2639",
+    AppType::MobileServer,
+    ""
+)]
+#[case(
+    app_info(u16cstr!("8.5.1.1"), None, 42),
+    "8.5.1.1",
+    AppType::Unknown,
+    ""
+)]
+fn test_get_platform_info(
+    #[case] raw: MockAppInfo,
+    #[case] app_version: &str,
+    #[case] application: AppType,
+    #[case] user_agent: &str,
+) {
+    let platform_info = MockPlatformInfo::new(Some(raw));
+    assert_eq!(
+        platform_info.as_platform_info().get_platform_info(),
+        Some(AppInfo {
+            app_version: app_version.to_owned(),
+            application,
+            user_agent: user_agent.to_owned(),
+        })
+    );
+}
+
+#[test]
+fn test_get_platform_info_not_provided() {
+    let platform_info = MockPlatformInfo::new(None);
+    assert_eq!(platform_info.as_platform_info().get_platform_info(), None);
+}
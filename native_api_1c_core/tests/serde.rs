@@ -0,0 +1,229 @@
+#[cfg(target_family = "unix")]
+use std::ffi::c_long;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use native_api_1c_core::{
+    ffi::provided_types::Tm,
+    interface::{DeserializeError, ParamValue, ParamValues},
+    widestring::U16String,
+};
+use rstest::rstest;
+use serde::Deserialize;
+use serde_json::json;
+use serde_test::{Configure, Token, assert_tokens};
+
+fn date_time() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 5, 17)
+        .unwrap()
+        .and_hms_opt(12, 30, 0)
+        .unwrap()
+}
+
+#[rstest]
+#[case(ParamValue::Empty, json!("Empty"))]
+#[case(ParamValue::Null, json!("Null"))]
+#[case(ParamValue::Bool(true), json!({ "Bool": true }))]
+#[case(ParamValue::I32(-5), json!({ "I32": -5 }))]
+#[case(ParamValue::U64(u64::MAX), json!({ "U64": u64::MAX }))]
+#[case(ParamValue::F64(0.5), json!({ "F64": 0.5 }))]
+#[case(ParamValue::from_str("привет"), json!({ "String": "привет" }))]
+#[case(ParamValue::Blob(vec![0, 1, 2, 255]), json!({ "Blob": "AAEC/w==" }))]
+#[case(ParamValue::Error(7), json!({ "Error": 7 }))]
+fn test_json(#[case] value: ParamValue, #[case] expected: serde_json::Value) {
+    assert_eq!(serde_json::to_value(&value).unwrap(), expected);
+    assert_eq!(
+        serde_json::from_value::<ParamValue>(expected).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn test_json_unpaired_surrogate() {
+    let value = ParamValue::String(U16String::from_vec(vec![0x61, 0xD800]));
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json, json!({ "String": [0x61, 0xD800] }));
+    assert_eq!(serde_json::from_value::<ParamValue>(json).unwrap(), value);
+}
+
+#[test]
+fn test_json_date() {
+    let value = ParamValue::Date(date_time().into());
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json, json!({ "Date": "2024-05-17T12:30:00" }));
+    assert_eq!(serde_json::from_value::<ParamValue>(json).unwrap(), value);
+
+    let json = serde_json::to_value(Tm::EMPTY_DATE).unwrap();
+    assert_eq!(json, json!("0001-01-01T00:00:00"));
+    let tm: Tm = serde_json::from_value(json).unwrap();
+    assert!(tm.is_empty_date());
+}
+
+#[cfg(target_family = "unix")]
+#[rstest]
+#[case(10800, "2024-05-17T12:30:00+03:00")]
+#[case(-16200, "2024-05-17T12:30:00-04:30")]
+fn test_json_date_offset(#[case] gmtoff: c_long, #[case] expected: &str) {
+    let tm = Tm {
+        gmtoff,
+        ..date_time().into()
+    };
+    let json = serde_json::to_value(tm).unwrap();
+    assert_eq!(json, json!(expected));
+
+    let decoded: Tm = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, tm);
+    assert_eq!(
+        decoded.utc_offset().unwrap().local_minus_utc(),
+        gmtoff as i32
+    );
+}
+
+#[rstest]
+#[case::date_only(json!("2024-05-17"))]
+#[case::nonexistent_date(json!("2024-02-30T00:00:00"))]
+#[case::year_zero(json!("0000-01-01T00:00:00"))]
+#[case::invalid_offset(json!("2024-05-17T12:30:00 MSK"))]
+#[case::raw_tm(json!({ "sec": 0, "min": 30, "hour": 12 }))]
+fn test_json_date_invalid(#[case] json: serde_json::Value) {
+    assert!(serde_json::from_value::<Tm>(json).is_err());
+}
+
+#[test]
+fn test_json_date_out_of_range() {
+    let tm = Tm {
+        mon: 12,
+        ..date_time().into()
+    };
+    assert!(serde_json::to_value(tm).is_err());
+}
+
+#[test]
+fn test_json_params() {
+    let params =
+        ParamValues::new(vec![ParamValue::I32(1), ParamValue::from_str("a")]);
+    let json = serde_json::to_value(&params).unwrap();
+    assert_eq!(json, json!([{ "I32": 1 }, { "String": "a" }]));
+
+    let decoded: ParamValues = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        decoded.iter().collect::<Vec<_>>(),
+        params.iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_compact() {
+    assert_tokens(
+        &ParamValue::Blob(vec![1, 2]).compact(),
+        &[
+            Token::NewtypeVariant {
+                name: "ParamValue",
+                variant: "Blob",
+            },
+            Token::Bytes(&[1, 2]),
+        ],
+    );
+    assert_tokens(
+        &ParamValue::from_str("ab").compact(),
+        &[
+            Token::NewtypeVariant {
+                name: "ParamValue",
+                variant: "String",
+            },
+            Token::Seq { len: Some(2) },
+            Token::U16(0x61),
+            Token::U16(0x62),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    Fast,
+    Slow,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Args {
+    count: u8,
+    ratio: f64,
+    name: String,
+    data: Vec<u8>,
+    mode: Mode,
+    since: Option<NaiveDateTime>,
+    until: Option<NaiveDateTime>,
+    comment: Option<String>,
+}
+
+#[test]
+fn test_decode_struct() {
+    let params = ParamValues::new(vec![
+        ParamValue::I32(5),
+        ParamValue::I32(2),
+        ParamValue::from_str("name"),
+        ParamValue::Blob(vec![1, 2]),
+        ParamValue::from_str("slow"),
+        ParamValue::Date(date_time().into()),
+        ParamValue::Date(Tm::EMPTY_DATE),
+        ParamValue::Empty,
+    ]);
+    assert_eq!(
+        params.decode::<Args>().unwrap(),
+        Args {
+            count: 5,
+            ratio: 2.0,
+            name: "name".to_string(),
+            data: vec![1, 2],
+            mode: Mode::Slow,
+            since: Some(date_time()),
+            until: None,
+            comment: None,
+        }
+    );
+}
+
+#[test]
+fn test_decode_tuple() {
+    let params =
+        ParamValues::new(vec![ParamValue::Bool(true), ParamValue::I64(-1)]);
+    assert_eq!(params.decode::<(bool, i64)>().unwrap(), (true, -1));
+    assert_eq!(
+        ParamValues::new(vec![ParamValue::from_str("fast")])
+            .decode::<(Mode,)>()
+            .unwrap(),
+        (Mode::Fast,)
+    );
+    assert_eq!(params.decode::<Vec<i32>>().ok(), None);
+}
+
+#[test]
+fn test_decode_invalid_param() {
+    let params =
+        ParamValues::new(vec![ParamValue::I32(1), ParamValue::I32(300)]);
+    let error = <(i32, u8)>::deserialize(&params).unwrap_err();
+    assert_eq!(error.index, Some(1));
+
+    let error = params.decode::<(i32, u8)>().unwrap_err();
+    assert!(
+        error
+            .description()
+            .starts_with("invalid value of parameter #2: "),
+        "{}",
+        error.description()
+    );
+}
+
+#[test]
+fn test_decode_param_count() {
+    let params = ParamValues::new(vec![ParamValue::I32(1); 3]);
+    assert_eq!(
+        <(i32, i32)>::deserialize(&params),
+        Err(DeserializeError {
+            index: None,
+            message: "invalid length 3, expected 2 parameters".to_string(),
+        })
+    );
+    assert!(params.decode::<(i32, i32, i32, i32)>().is_err());
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use std::{panic, time::Duration};
+
+use native_api_1c_core::{
+    ffi::connection::Connection,
+    interface::{ProgressFormat, ProgressReporter, StatusLineGuard},
+};
+use rstest::rstest;
+
+use common::connection::MockConnection;
+
+fn shown(connection: &MockConnection) -> Vec<Option<String>> {
+    connection.status_line.take()
+}
+
+fn set(text: &str) -> Option<String> {
+    Some(text.to_owned())
+}
+
+#[rstest]
+#[case::steps(ProgressFormat::Steps, 3, Some(10), "Export: step 3 of 10")]
+#[case::steps_unknown_total(ProgressFormat::Steps, 3, None, "Export: step 3")]
+#[case::steps_empty(ProgressFormat::Steps, 0, Some(0), "Export: step 0 of 0")]
+#[case::steps_over_total(
+    ProgressFormat::Steps,
+    12,
+    Some(10),
+    "Export: step 12 of 10"
+)]
+#[case::percent(ProgressFormat::Percent, 1, Some(3), "Export: 33%")]
+#[case::percent_start(ProgressFormat::Percent, 0, Some(10), "Export: 0%")]
+#[case::percent_unknown_total(
+    ProgressFormat::Percent,
+    3,
+    None,
+    "Export: step 3"
+)]
+#[case::percent_empty(ProgressFormat::Percent, 0, Some(0), "Export: 100%")]
+#[case::percent_over_total(
+    ProgressFormat::Percent,
+    12,
+    Some(10),
+    "Export: 100%"
+)]
+#[case::percent_large(
+    ProgressFormat::Percent,
+    u64::MAX - 1,
+    Some(u64::MAX),
+    "Export: 99%"
+)]
+fn test_status_text(
+    #[case] format: ProgressFormat,
+    #[case] position: u64,
+    #[case] total: Option<u64>,
+    #[case] expected: &str,
+) {
+    let connection = MockConnection::new(None);
+    let mut reporter =
+        ProgressReporter::new(connection.as_connection(), "Export", total)
+            .with_format(format)
+            .with_min_interval(Duration::ZERO);
+    reporter.set_position(position);
+
+    assert_eq!(reporter.status_text(), expected);
+    assert_eq!(shown(&connection).last(), Some(&set(expected)));
+}
+
+#[test]
+fn test_throttle() {
+    let connection = MockConnection::new(None);
+    let mut reporter =
+        ProgressReporter::new(connection.as_connection(), "Export", Some(4))
+            .with_min_interval(Duration::from_secs(3600));
+    assert_eq!(
+        shown(&connection),
+        [set("Export"), set("Export: step 0 of 4")]
+    );
+
+    // steps within the interval are not shown, except the last one
+    reporter.inc();
+    reporter.inc();
+    reporter.inc();
+    assert!(shown(&connection).is_empty());
+    reporter.inc();
+    assert_eq!(shown(&connection), [set("Export: step 4 of 4")]);
+
+    // changes of text and total are always shown
+    reporter.set_text("Import");
+    reporter.set_total(None);
+    reporter.inc();
+    assert_eq!(
+        shown(&connection),
+        [set("Import: step 4 of 4"), set("Import: step 4")]
+    );
+}
+
+#[test]
+fn test_throttle_disabled() {
+    let connection = MockConnection::new(None);
+    let mut reporter =
+        ProgressReporter::new(connection.as_connection(), "Export", None)
+            .with_min_interval(Duration::ZERO);
+    shown(&connection);
+
+    reporter.inc();
+    reporter.advance(2);
+    assert_eq!(
+        shown(&connection),
+        [set("Export: step 1"), set("Export: step 3")]
+    );
+}
+
+fn export(connection: &Connection, fail_at: u64) -> Result<(), String> {
+    let mut reporter = ProgressReporter::new(connection, "Export", Some(10));
+    for step in 0..10 {
+        if step == fail_at {
+            return Err(format!("failed at step {step}"));
+        }
+        reporter.inc();
+    }
+    Ok(())
+}
+
+#[rstest]
+#[case::succeeded(10)]
+#[case::failed(5)]
+fn test_reporter_resets_on_drop(#[case] fail_at: u64) {
+    let connection = MockConnection::new(None);
+    let result = export(connection.as_connection(), fail_at);
+
+    assert_eq!(result.is_err(), fail_at < 10);
+    assert_eq!(shown(&connection).last(), Some(&None));
+}
+
+#[test]
+fn test_guard_resets_on_drop() {
+    let connection = MockConnection::new(None);
+    {
+        let status_line =
+            StatusLineGuard::new(connection.as_connection(), "Export");
+        assert!(status_line.set("Export: done"));
+    }
+    assert_eq!(
+        shown(&connection),
+        [set("Export"), set("Export: done"), None]
+    );
+}
+
+#[test]
+fn test_guard_resets_on_panic() {
+    let connection = MockConnection::new(None);
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let _status_line =
+            StatusLineGuard::new(connection.as_connection(), "Export");
+        panic!("export failed");
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(shown(&connection), [set("Export"), None]);
+}
@@ -0,0 +1,427 @@
+mod common;
+
+use std::{ffi::c_long, fmt::Write as _, io::Write as _, ptr};
+
+use chrono::NaiveDate;
+
+use native_api_1c_core::{
+    ffi::{
+        api_version::{ApiVersion, PStrEncoding},
+        memory_manager::AllocationError,
+        platform_buf::{PlatformBuf, PlatformStr},
+        provided_types::{
+            DataBlob, DataPStr, DataStr, TVariant, VariantType, VariantValue,
+        },
+    },
+    interface::{ParamValue, ParamValueRef, PlatformValue},
+    widestring::U16String,
+};
+use rstest::rstest;
+
+use common::memory::MockMemoryManager;
+
+fn owns_data(value: &ParamValue) -> bool {
+    matches!(value, ParamValue::String(_) | ParamValue::Blob(_))
+}
+
+#[test]
+fn test_scalar_bits_are_not_freed() {
+    let mem = MockMemoryManager::new();
+    let mut variant = TVariant::default();
+    variant.value.u64 = 0xDEAD_BEEF;
+    variant.vt = VariantType::Int64;
+
+    unsafe { variant.update_to_str(mem.as_mem(), &[0x61]) }.unwrap();
+    mem.assert_no_invalid_frees();
+    assert_eq!(mem.live_count(), 1);
+
+    unsafe { variant.free_data(mem.as_mem()) };
+    variant.update_to_i64(0xDEAD_BEEF);
+    unsafe { variant.update_to_blob(mem.as_mem(), &[1, 2]) }.unwrap();
+    mem.assert_no_invalid_frees();
+    assert_eq!(mem.live_count(), 1);
+}
+
+#[rstest]
+#[case(vec![
+    ParamValue::from_str("a"),
+    ParamValue::from_str("bb"),
+    ParamValue::Blob(vec![1, 2, 3]),
+    ParamValue::I32(5),
+    ParamValue::Empty,
+    ParamValue::Blob(vec![]),
+    ParamValue::from_str(""),
+    ParamValue::from_str("c"),
+])]
+#[case(vec![
+    ParamValue::Bool(true),
+    ParamValue::Blob(vec![1]),
+    ParamValue::Null,
+    ParamValue::from_str("a"),
+    ParamValue::F64(0.5),
+])]
+fn test_repeated_writes(#[case] values: Vec<ParamValue>) {
+    let mem = MockMemoryManager::new();
+    let mut variant = TVariant::default();
+
+    for value in &values {
+        variant.update_from_return(mem.as_mem(), value).unwrap();
+        assert_eq!(&ParamValue::from(&variant), value);
+        assert_eq!(mem.live_count(), owns_data(value) as usize);
+    }
+
+    unsafe { variant.free_data(mem.as_mem()) };
+    assert_eq!(variant.vt, VariantType::Empty);
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+}
+
+#[rstest]
+#[case(ParamValue::from_str("out"))]
+#[case(ParamValue::I32(1))]
+fn test_platform_string_is_freed(#[case] value: ParamValue) {
+    let mem = MockMemoryManager::new();
+
+    // parameter, passed by 1C platform
+    let mut variant = TVariant::default();
+    let ptr = mem.as_mem().alloc_str(3).unwrap();
+    variant.value.data_str.ptr = ptr.as_ptr();
+    variant.value.data_str.len = 2;
+    variant.vt = VariantType::WStr;
+
+    variant.update_from_return(mem.as_mem(), &value).unwrap();
+    assert!(!mem.live.borrow().contains_key(&(ptr.as_ptr() as usize)));
+    assert_eq!(mem.live_count(), owns_data(&value) as usize);
+    mem.assert_no_invalid_frees();
+}
+
+#[test]
+fn test_allocation_error() {
+    let mem = MockMemoryManager::new();
+    let mut variant = TVariant::default();
+    let value = ParamValue::from_str("kept");
+    variant.update_from_return(mem.as_mem(), &value).unwrap();
+
+    mem.fail.set(true);
+    assert_eq!(
+        variant.update_from_return(mem.as_mem(), &ParamValue::from_str("new")),
+        Err(AllocationError)
+    );
+    assert_eq!(
+        variant.update_from_return(mem.as_mem(), &ParamValue::Blob(vec![1])),
+        Err(AllocationError)
+    );
+    assert_eq!(ParamValue::from(&variant), value);
+    assert_eq!(mem.live_count(), 1);
+
+    mem.fail.set(false);
+    unsafe { variant.free_data(mem.as_mem()) };
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+}
+
+#[test]
+fn test_borrowed_value_is_not_copied() {
+    let mem = MockMemoryManager::new();
+    let mut variant = TVariant::default();
+
+    let value = ParamValue::from_str("borrowed");
+    variant.update_from_return(mem.as_mem(), &value).unwrap();
+    let value_ref = ParamValueRef::from(&variant);
+    let str_ref = value_ref.as_str().unwrap();
+    assert_eq!(str_ref.as_ptr(), unsafe { variant.value.data_str.ptr });
+    assert_eq!(value_ref.into_owned(), value);
+
+    let value = ParamValue::Blob(vec![1, 2, 3]);
+    variant.update_from_return(mem.as_mem(), &value).unwrap();
+    let value_ref = ParamValueRef::from(&variant);
+    assert_eq!(value_ref.as_blob().unwrap().as_ptr(), unsafe {
+        variant.value.data_blob.ptr
+    });
+    assert_eq!(value_ref.into_owned(), value);
+
+    unsafe { variant.free_data(mem.as_mem()) };
+    mem.assert_no_invalid_frees();
+}
+
+#[test]
+fn test_platform_str_is_passed_without_copy() {
+    // `PlatformStr` keeps `&'static MemoryManager`, as 1C platform passes it
+    let mem = MockMemoryManager::leaked();
+    let mut variant = TVariant::default();
+    variant
+        .update_from_return(mem.as_mem(), &ParamValue::from_str("old"))
+        .unwrap();
+
+    let mut report = PlatformStr::new(mem.as_mem());
+    for i in 0..100 {
+        writeln!(report, "строка {i}").unwrap();
+    }
+    report.push_utf16(&[0, 0x61]).unwrap();
+    let expected = report.as_ustr().to_owned();
+    // previous blocks are freed, when the string grows
+    assert_eq!(mem.live_count(), 2);
+
+    let ptr = report.as_ustr().as_ptr();
+    variant
+        .update_from_platform(mem.as_mem(), PlatformValue::from(report))
+        .unwrap();
+    assert_eq!(unsafe { variant.value.data_str.ptr }.cast_const(), ptr);
+    assert_eq!(
+        ParamValue::from(&variant),
+        ParamValue::String(expected.clone())
+    );
+    assert_eq!(unsafe { *ptr.add(expected.len()) }, 0);
+    assert_eq!(mem.live_count(), 1);
+
+    unsafe { variant.free_data(mem.as_mem()) };
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+}
+
+#[test]
+fn test_platform_buf() {
+    let mem = MockMemoryManager::leaked();
+    let mut variant = TVariant::default();
+
+    let mut buf = PlatformBuf::with_capacity(mem.as_mem(), 4).unwrap();
+    buf.write_all(&[1, 2, 3, 4]).unwrap();
+    let ptr = buf.as_slice().as_ptr();
+    variant
+        .update_from_platform(mem.as_mem(), PlatformValue::from(buf))
+        .unwrap();
+    assert_eq!(unsafe { variant.value.data_blob.ptr }.cast_const(), ptr);
+    assert_eq!(
+        ParamValue::from(&variant),
+        ParamValue::Blob(vec![1, 2, 3, 4])
+    );
+
+    // empty buffer is still allocated, so that 1C platform gets a valid pointer
+    variant
+        .update_from_platform(
+            mem.as_mem(),
+            PlatformBuf::new(mem.as_mem()).into(),
+        )
+        .unwrap();
+    assert_eq!(ParamValue::from(&variant), ParamValue::Blob(vec![]));
+    assert_eq!(mem.live_count(), 1);
+
+    // dropped buffers are freed
+    let mut buf = PlatformBuf::new(mem.as_mem());
+    buf.write_all(&[0; 1000]).unwrap();
+    drop(buf);
+    assert_eq!(mem.live_count(), 1);
+
+    mem.fail.set(true);
+    let mut buf = PlatformBuf::new(mem.as_mem());
+    assert!(buf.write_all(&[1]).is_err());
+    mem.fail.set(false);
+
+    unsafe { variant.free_data(mem.as_mem()) };
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+}
+
+#[rstest]
+#[case(ApiVersion::V1, VariantType::PStr)]
+#[case(ApiVersion::V2, VariantType::WStr)]
+fn test_api_version_strings(
+    #[case] version: ApiVersion,
+    #[case] vt: VariantType,
+) {
+    assert_eq!(ApiVersion::from_info(version.info()), version);
+
+    let mem = MockMemoryManager::leaked();
+    let mut variant = TVariant::default();
+
+    let value = ParamValue::from_str("Ёлка");
+    version
+        .write_value(PStrEncoding::Utf8, &mut variant, mem.as_mem(), &value)
+        .unwrap();
+    assert_eq!(variant.vt, vt);
+    assert_eq!(ParamValue::from(&variant), value);
+
+    let mut report = PlatformStr::new(mem.as_mem());
+    report.push_str("отчет").unwrap();
+    version
+        .write_platform_value(
+            PStrEncoding::Utf8,
+            &mut variant,
+            mem.as_mem(),
+            report.into(),
+        )
+        .unwrap();
+    assert_eq!(variant.vt, vt);
+    assert_eq!(ParamValue::from(&variant), ParamValue::from_str("отчет"));
+
+    // other values are the same in all versions
+    version
+        .write_value(
+            PStrEncoding::Utf8,
+            &mut variant,
+            mem.as_mem(),
+            &ParamValue::Blob(vec![1]),
+        )
+        .unwrap();
+    assert_eq!(variant.vt, VariantType::Blob);
+    assert_eq!(mem.live_count(), 1);
+
+    unsafe { variant.free_data(mem.as_mem()) };
+    assert_eq!(mem.live_count(), 0);
+    mem.assert_no_invalid_frees();
+}
+
+#[rstest]
+#[case(PStrEncoding::Utf8, "Ёлка", "Ёлка".as_bytes())]
+#[case(PStrEncoding::Windows1251, "Ёлка", b"\xA8\xEB\xEA\xE0")]
+#[case(PStrEncoding::Windows1251, "Яя № 5 — €", b"\xDF\xFF \xB9 5 \x97 \x88")]
+fn test_pstr_encoding(
+    #[case] encoding: PStrEncoding,
+    #[case] value: &str,
+    #[case] bytes: &[u8],
+) {
+    let value = U16String::from_str(value);
+    assert_eq!(encoding.encode(&value), bytes);
+    assert_eq!(encoding.decode(bytes), value);
+
+    let mem = MockMemoryManager::new();
+    let mut variant = TVariant::default();
+    unsafe { variant.update_to_pstr(mem.as_mem(), bytes) }.unwrap();
+    assert_eq!(variant.as_pstr(), Some(bytes));
+    assert_eq!(
+        encoding.read_value(&variant).into_owned(),
+        ParamValue::String(value)
+    );
+
+    unsafe { variant.free_data(mem.as_mem()) };
+    mem.assert_no_invalid_frees();
+}
+
+#[test]
+fn test_pstr_encoding_unmappable() {
+    let value = U16String::from_str("日本 Ё");
+    assert_eq!(PStrEncoding::Windows1251.encode(&value), b"?? \xA8");
+    assert_eq!(
+        PStrEncoding::Utf8.decode(b"\xFF"),
+        U16String::from_str("\u{FFFD}")
+    );
+}
+
+fn variant(vt: VariantType, value: VariantValue) -> TVariant {
+    TVariant {
+        value,
+        elements: 0,
+        vt,
+    }
+}
+
+fn date(y: i32, m: u32, d: u32, h: u32, min: u32, sec: u32) -> ParamValue {
+    let date_time = NaiveDate::from_ymd_opt(y, m, d)
+        .unwrap()
+        .and_hms_opt(h, min, sec)
+        .unwrap();
+    ParamValue::Date(date_time.into())
+}
+
+#[rstest]
+#[case(variant(VariantType::Empty, VariantValue { i32: 7 }), ParamValue::Empty)]
+#[case(variant(VariantType::Null, VariantValue { i32: 7 }), ParamValue::Null)]
+#[case(variant(VariantType::Bool, VariantValue { bool: true }), ParamValue::Bool(true))]
+#[case(variant(VariantType::Int8, VariantValue { i8: i8::MIN }), ParamValue::I32(-128))]
+#[case(variant(VariantType::Int16, VariantValue { i16: i16::MIN }), ParamValue::I32(-32768))]
+#[case(variant(VariantType::Int32, VariantValue { i32: i32::MIN }), ParamValue::I32(i32::MIN))]
+#[case(variant(VariantType::Int, VariantValue { i32: -1 }), ParamValue::I32(-1))]
+#[case(variant(VariantType::Int64, VariantValue { i64: i64::MIN }), ParamValue::I64(i64::MIN))]
+#[case(variant(VariantType::UInt8, VariantValue { u8: u8::MAX }), ParamValue::I32(255))]
+#[case(variant(VariantType::UInt16, VariantValue { u16: u16::MAX }), ParamValue::I32(65535))]
+#[case(
+    variant(VariantType::UInt32, VariantValue { u32: u32::MAX }),
+    ParamValue::I64(u32::MAX.into())
+)]
+#[case(
+    variant(VariantType::UInt, VariantValue { u32: u32::MAX }),
+    ParamValue::I64(u32::MAX.into())
+)]
+#[case(
+    variant(VariantType::UInt64, VariantValue { u64: u64::MAX }),
+    ParamValue::U64(u64::MAX)
+)]
+#[case(variant(VariantType::Float, VariantValue { f32: -1.5 }), ParamValue::F64(-1.5))]
+#[case(variant(VariantType::Double, VariantValue { f64: 0.1 }), ParamValue::F64(0.1))]
+#[case(
+    variant(VariantType::Error, VariantValue { error_code: -3 }),
+    ParamValue::Error(-3)
+)]
+#[case(
+    variant(VariantType::HResult, VariantValue { hresult: 0x8000_4005_u32 as c_long }),
+    ParamValue::Error(0x8000_4005_u32 as i32)
+)]
+#[case(
+    variant(VariantType::ClsID, VariantValue { clsid: [0xAB; 16] }),
+    ParamValue::ClsId([0xAB; 16])
+)]
+#[case(variant(VariantType::Interface, VariantValue { u64: 1 }), ParamValue::Empty)]
+#[case(variant(VariantType::Variant, VariantValue { u64: 1 }), ParamValue::Empty)]
+fn test_decode_scalar(#[case] variant: TVariant, #[case] expected: ParamValue) {
+    assert_eq!(ParamValue::from(&variant), expected);
+}
+
+#[rstest]
+#[case::epoch(0.0, date(1899, 12, 30, 0, 0, 0))]
+#[case::noon(45000.5, date(2023, 3, 15, 12, 0, 0))]
+// time of day of negative dates is still counted forward from midnight
+#[case::negative(-1.25, date(1899, 12, 29, 6, 0, 0))]
+#[case::negative_fraction(-0.5, date(1899, 12, 30, 12, 0, 0))]
+#[case::rounds_to_midnight(1.999_999_99, date(1900, 1, 1, 0, 0, 0))]
+#[case::rounds_to_second(2.000_005_7, date(1900, 1, 1, 0, 0, 0))]
+#[case::out_of_chrono_range(99_000_000.0, ParamValue::F64(99_000_000.0))]
+#[case::out_of_range(1e12, ParamValue::F64(1e12))]
+#[case::infinite(f64::INFINITY, ParamValue::F64(f64::INFINITY))]
+fn test_decode_ole_date(#[case] value: f64, #[case] expected: ParamValue) {
+    let variant = variant(VariantType::Date, VariantValue { date: value });
+    assert_eq!(ParamValue::from(&variant), expected);
+}
+
+#[test]
+fn test_decode_ole_date_nan() {
+    let variant = variant(VariantType::Date, VariantValue { date: f64::NAN });
+    assert!(matches!(
+        ParamValue::from(&variant),
+        ParamValue::F64(value) if value.is_nan()
+    ));
+}
+
+#[rstest]
+#[case(variant(
+    VariantType::WStr,
+    VariantValue { data_str: DataStr { ptr: ptr::null_mut(), len: 5 } },
+), ParamValue::from_str(""))]
+#[case(variant(
+    VariantType::PStr,
+    VariantValue { data_pstr: DataPStr { ptr: ptr::null_mut(), len: 5 } },
+), ParamValue::from_str(""))]
+#[case(variant(
+    VariantType::Blob,
+    VariantValue { data_blob: DataBlob { ptr: ptr::null_mut(), len: 5 } },
+), ParamValue::Blob(vec![]))]
+fn test_decode_null_pointer(
+    #[case] variant: TVariant,
+    #[case] expected: ParamValue,
+) {
+    assert_eq!(ParamValue::from(&variant), expected);
+}
+
+#[test]
+fn test_decode_pstr() {
+    let mut bytes = "Ёлка".as_bytes().to_vec();
+    let variant = variant(
+        VariantType::PStr,
+        VariantValue {
+            data_pstr: DataPStr {
+                ptr: bytes.as_mut_ptr().cast(),
+                len: bytes.len() as u32,
+            },
+        },
+    );
+    assert_eq!(ParamValue::from(&variant), ParamValue::from_str("Ёлка"));
+}
@@ -1,25 +1,51 @@
 pub const BOOL_TYPE: &str = "Bool";
 pub const I32_TYPE: &str = "Int";
+pub const I64_TYPE: &str = "Long";
+pub const U64_TYPE: &str = "ULong";
 pub const F64_TYPE: &str = "Float";
+pub const DECIMAL_TYPE: &str = "Decimal";
 pub const STRING_TYPE: &str = "Str";
 pub const DATE_TYPE: &str = "Date";
+pub const OPTIONAL_DATE_TYPE: &str = "OptionalDate";
+pub const DATE_TZ_TYPE: &str = "DateTz";
+pub const DATE_UTC_TYPE: &str = "DateUtc";
 pub const BLOB_TYPE: &str = "Blob";
+pub const STRING_REF_TYPE: &str = "StrRef";
+pub const BLOB_REF_TYPE: &str = "BlobRef";
+pub const STRING_BUF_TYPE: &str = "StrBuf";
+pub const BLOB_BUF_TYPE: &str = "BlobBuf";
 pub const UNTYPED_TYPE: &str = "None";
 
 pub const ALL_RETURN_TYPES: &[&str] = &[
     BOOL_TYPE,
     I32_TYPE,
+    I64_TYPE,
+    U64_TYPE,
     F64_TYPE,
+    DECIMAL_TYPE,
     STRING_TYPE,
     DATE_TYPE,
+    OPTIONAL_DATE_TYPE,
+    DATE_TZ_TYPE,
+    DATE_UTC_TYPE,
     BLOB_TYPE,
+    STRING_BUF_TYPE,
+    BLOB_BUF_TYPE,
     UNTYPED_TYPE,
 ];
 pub const ALL_ARG_TYPES: &[&str] = &[
     BOOL_TYPE,
     I32_TYPE,
+    I64_TYPE,
+    U64_TYPE,
     F64_TYPE,
+    DECIMAL_TYPE,
     STRING_TYPE,
     DATE_TYPE,
+    OPTIONAL_DATE_TYPE,
+    DATE_TZ_TYPE,
+    DATE_UTC_TYPE,
     BLOB_TYPE,
+    STRING_REF_TYPE,
+    BLOB_REF_TYPE,
 ];
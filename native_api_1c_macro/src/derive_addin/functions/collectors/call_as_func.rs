@@ -44,11 +44,23 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for CallAsFuncCollector {
                 params: &mut native_api_1c::native_api_1c_core::interface::ParamValues,
             ) -> native_api_1c::native_api_1c_core::interface::AddInWrapperResult<
                 native_api_1c::native_api_1c_core::interface::ParamValue
+            > {
+                params
+                    .with_ref(|params| self.call_as_func_ref(method_num, params))
+                    .map(native_api_1c::native_api_1c_core::interface::PlatformValue::into_param_value)
+            }
+
+            fn call_as_func_ref(
+                &mut self,
+                method_num: usize,
+                params: &mut native_api_1c::native_api_1c_core::interface::ParamValuesRef,
+            ) -> native_api_1c::native_api_1c_core::interface::AddInWrapperResult<
+                native_api_1c::native_api_1c_core::interface::PlatformValue
             > {
                 match method_num {
                     #body
                     _ => {
-                        Err(())
+                        Err(native_api_1c::native_api_1c_core::interface::AddInError::method_not_found(method_num))
                     }
                 }
             }
@@ -35,11 +35,19 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for CallAsProcCollector {
                 &mut self,
                 method_num: usize,
                 params: &mut native_api_1c::native_api_1c_core::interface::ParamValues,
+            ) -> native_api_1c::native_api_1c_core::interface::AddInWrapperResult<()> {
+                params.with_ref(|params| self.call_as_proc_ref(method_num, params))
+            }
+
+            fn call_as_proc_ref(
+                &mut self,
+                method_num: usize,
+                params: &mut native_api_1c::native_api_1c_core::interface::ParamValuesRef,
             ) -> native_api_1c::native_api_1c_core::interface::AddInWrapperResult<()> {
                 match method_num {
                     #body
                     _ => {
-                        Err(())
+                        Err(native_api_1c::native_api_1c_core::interface::AddInError::method_not_found(method_num))
                     }
                 }
             }
@@ -22,19 +22,26 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for FindMethodCollector {
         let mut find_method_body = TokenStream::new();
 
         for (func_index, func_desc) in iter {
-            let name_slice_const = &func_desc.name_slice_const;
-            let name_ru_slice_const = &func_desc.name_ru_slice_const;
+            let name_folded_const = &func_desc.name_folded_const;
+            let name_ru_folded_const = &func_desc.name_ru_folded_const;
 
             find_method_body.extend(quote_spanned! { func_desc.ident.span() =>
-                Self::#name_slice_const | Self::#name_ru_slice_const => { 
+                Self::#name_folded_const | Self::#name_ru_folded_const => { 
                     Some(#func_index)
                 },
             });
         }
 
         let find_method_definition = quote! {
+            // `name` and `name_ru` of the same item may be equal
+            #[allow(unreachable_patterns)]
             fn find_method(&self, name: &native_api_1c::native_api_1c_core::widestring::U16CStr) -> Option<usize> {
-                match name.as_slice_with_nul() {
+                // names are checked for collisions at compile time
+                #[allow(clippy::let_unit_value)]
+                let () = Self::ADDIN_FUNC_NAMES_UNIQUE;
+                // 1C platform looks names up ignoring case
+                let name = native_api_1c::native_api_1c_core::interface::FoldedName::new(name.as_slice_with_nul());
+                match name.as_slice() {
                     #find_method_body
                     _ => {
                         None
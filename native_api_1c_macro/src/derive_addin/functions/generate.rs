@@ -2,8 +2,8 @@ use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::{spanned::Spanned, Ident};
 
-use native_api_1c_core::interface::ParamValue;
-use crate::derive_addin::parsers::ParamValueWrapper;
+use native_api_1c_core::interface::{ParamType, ParamValue};
+use crate::derive_addin::parsers::{coerce_args, date_conversion, ParamValueWrapper};
 
 use super::{FuncArgumentDesc, FuncDesc, FuncParamType};
 
@@ -52,7 +52,8 @@ pub fn func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
     if let Some(set_to) = set_to {
         if func.return_value.result {
             func_call.extend(quote_spanned! { func.ident.span() => 
-                let call_result = (#func_call_fn_with_args)?;
+                let call_result = (#func_call_fn_with_args)
+                    .map_err(Into::<native_api_1c::native_api_1c_core::interface::AddInError>::into)?;
             });
         } else {
             func_call.extend(quote_spanned! { func.ident.span() => 
@@ -60,15 +61,24 @@ pub fn func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
             })
         };
 
-        let return_ty = func.return_value.ty.clone().unwrap();
-        let from_type_fn = Ident::new(ParamValue::from_type_fn_name(return_ty), func.ident.span());
-        func_call.extend(quote_spanned! { func.ident.span() =>
-            let #set_to = native_api_1c::native_api_1c_core::interface::ParamValue::#from_type_fn(call_result);
-        });
+        let return_ty = func.return_value.ty.unwrap();
+        if func.return_value.platform {
+            func_call.extend(quote_spanned! { func.ident.span() =>
+                let #set_to = native_api_1c::native_api_1c_core::interface::PlatformValue::from(call_result);
+            });
+        } else {
+            let from_type_fn = Ident::new(ParamValue::from_type_fn_name(return_ty), func.ident.span());
+            func_call.extend(quote_spanned! { func.ident.span() =>
+                let #set_to = native_api_1c::native_api_1c_core::interface::PlatformValue::from(
+                    native_api_1c::native_api_1c_core::interface::ParamValue::#from_type_fn(call_result)
+                );
+            });
+        }
     } else {
         if func.return_value.result {
             func_call.extend(quote_spanned! { func.ident.span() => 
-                (#func_call_fn_with_args)?;
+                (#func_call_fn_with_args)
+                    .map_err(Into::<native_api_1c::native_api_1c_core::interface::AddInError>::into)?;
             });
         } else {
             func_call.extend(quote_spanned! { func.ident.span() => 
@@ -90,11 +100,38 @@ fn gen_param_prep(
     param_ident: &Ident,
     param_val_ident: &Ident,
 ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
-    let FuncParamType::PlatformType(param_ty) = &param.ty else {
-        panic!("SelfType is not allowed here");
+    let param_ty = match &param.ty {
+        FuncParamType::PlatformType(param_ty) => param_ty,
+        FuncParamType::Borrowed(param_ty) => {
+            return gen_borrowed_param_prep(*param_ty, param, param_index, param_ident, param_val_ident)
+        }
+        FuncParamType::SelfType => panic!("SelfType is not allowed here"),
     };
 
-    let mut pre_call = quote! {};
+    // parameters are borrowed from 1C platform, strings and blobs are copied only into
+    // the argument, other values are made owned for conversion, e.g. for coercion
+    let borrowed_to_type = match param_ty {
+        ParamType::String => Some(quote! { as_str().map(|v| v.to_string_lossy()) }),
+        ParamType::Blob => Some(quote! { as_blob().map(|v| v.to_vec()) }),
+        _ => None,
+    };
+    let into_owned = match borrowed_to_type {
+        Some(_) => quote! {},
+        None => quote! { .into_owned() },
+    };
+    let mut pre_call = quote_spanned! { param.span =>
+        let #param_val_ident = params
+            .value(#param_index)
+            .ok_or_else(|| native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index))?
+            #into_owned;
+    };
+
+    let param_ref = match coerce_args(*param_ty, param.coercion.unwrap_or_default()) {
+        Some(coerce_args) => quote_spanned! { param.span =>
+            &#param_val_ident.coerce_to(#coerce_args)
+        },
+        None => quote_spanned! { param.span => &#param_val_ident },
+    };
 
     let param_value = if let Some(ParamValueWrapper{ ty: none_value_ty, value: none_value }) = &param.optional {
         let to_optional_type_fn = Ident::new( ParamValue::to_optional_type_fn_name(*param_ty), param.span );
@@ -104,17 +141,52 @@ fn gen_param_prep(
             let none_value = native_api_1c::native_api_1c_core::interface::ParamValue::#from_type_fn(#none_value);
         });
 
+        match &borrowed_to_type {
+            Some(borrowed_to_type) => quote_spanned! { param.span =>
+                if #param_val_ident == none_value.as_value_ref() {
+                    None
+                } else {
+                    Some(
+                        #param_val_ident.#borrowed_to_type
+                            .ok_or_else(|| native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index))?
+                    )
+                }
+                .into()
+            },
+            None => quote_spanned! { param.span =>
+                native_api_1c::native_api_1c_core::interface::ParamValue::#to_optional_type_fn(#param_ref, &none_value)
+                    .ok_or_else(|| native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index))?
+                    .into() 
+            },
+        }
+    } else if let Some(borrowed_to_type) = &borrowed_to_type {
+        quote_spanned! { param.span =>
+            #param_val_ident.#borrowed_to_type
+            .ok_or_else(|| native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index))?
+            .into()
+        }
+    } else if *param_ty == ParamType::Decimal {
+        // keeps the reason of failed conversion, e.g. overflow
+        quote_spanned! { param.span =>
+            native_api_1c::native_api_1c_core::interface::ParamValue::try_to_decimal(&#param_val_ident)
+            .map_err(|err| native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index).with_source(err))?
+        }
+    } else if let Some(convert) = date_conversion(
+        *param_ty,
+        quote! { native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index) },
+        param.span,
+    ) {
         quote_spanned! { param.span =>
-            native_api_1c::native_api_1c_core::interface::ParamValue::#to_optional_type_fn(&params[#param_index], &none_value)
-                .ok_or(())?
-                .into() 
+            native_api_1c::native_api_1c_core::interface::ParamValue::to_date(&#param_val_ident)
+            .ok_or_else(|| native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index))?
+            #convert
         }
     } else {
         let to_type_fn = Ident::new( ParamValue::to_type_fn_name(*param_ty), param.span );
 
         quote_spanned! { param.span =>
-            native_api_1c::native_api_1c_core::interface::ParamValue::#to_type_fn(&params[#param_index])
-            .ok_or(())?
+            native_api_1c::native_api_1c_core::interface::ParamValue::#to_type_fn(#param_ref)
+            .ok_or_else(|| native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index))?
             .into() 
         }
     };
@@ -135,9 +207,39 @@ fn gen_param_prep(
     } else {
         let set_type_fn = Ident::new(ParamValue::set_type_fn_name(*param_ty), param.span);
         quote_spanned! { param.span =>
-            params[#param_index].#set_type_fn( #param_val_ident );
+            {
+                let mut out_value = native_api_1c::native_api_1c_core::interface::ParamValue::Empty;
+                out_value.#set_type_fn( #param_val_ident );
+                params.set(#param_index, out_value)?;
+            }
         }
     };
 
     (pre_call, post_call)
 }
+
+/// `&U16Str` and `&[u8]` arguments, that borrow memory of 1C platform
+fn gen_borrowed_param_prep(
+    param_ty: ParamType,
+    param: &FuncArgumentDesc,
+    param_index: usize,
+    param_ident: &Ident,
+    param_val_ident: &Ident,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let as_type_fn = match param_ty {
+        ParamType::String => quote! { as_str },
+        ParamType::Blob => quote! { as_blob },
+        _ => panic!("only String and Blob can be borrowed"),
+    };
+
+    let pre_call = quote_spanned! { param.span =>
+        let #param_val_ident = params
+            .value(#param_index)
+            .ok_or_else(|| native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index))?;
+        let #param_ident = #param_val_ident
+            .#as_type_fn()
+            .ok_or_else(|| native_api_1c::native_api_1c_core::interface::AddInError::invalid_param(#param_index))?;
+    };
+
+    (pre_call, quote! {})
+}
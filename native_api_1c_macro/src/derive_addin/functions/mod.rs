@@ -4,12 +4,15 @@ use darling::FromMeta;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
 
-use native_api_1c_core::interface::ParamType;
+use native_api_1c_core::interface::{Coercion, ParamType};
 
 use crate::derive_addin::parsers::ParamValueWrapper;
 
 use super::{
-    constants::{BLOB_TYPE, BOOL_TYPE, DATE_TYPE, F64_TYPE, I32_TYPE, STRING_TYPE},
+    constants::{
+        BLOB_REF_TYPE, BLOB_TYPE, BOOL_TYPE, DATE_TYPE, DATE_TZ_TYPE, DATE_UTC_TYPE, DECIMAL_TYPE,
+        F64_TYPE, I32_TYPE, I64_TYPE, OPTIONAL_DATE_TYPE, STRING_REF_TYPE, STRING_TYPE, U64_TYPE,
+    },
 };
 
 pub mod collectors;
@@ -25,8 +28,8 @@ pub struct FuncDesc {
 
     pub name_const: TokenStream,
     pub name_ru_const: TokenStream,
-    pub name_slice_const: TokenStream,
-    pub name_ru_slice_const: TokenStream,
+    pub name_folded_const: TokenStream,
+    pub name_ru_folded_const: TokenStream,
 
     pub params: Vec<FuncArgumentDesc>,
     pub return_value: ReturnTypeDesc,
@@ -52,6 +55,8 @@ pub struct FuncArgumentDesc {
     pub ty: FuncParamType,
     pub default: Option<TokenStream>,
     pub optional: Option<ParamValueWrapper>,
+    /// Coercion of the argument, component coercion is used if not set
+    pub coercion: Option<Coercion>,
     pub out_param: bool,
     pub span: Span,
 }
@@ -59,6 +64,8 @@ pub struct FuncArgumentDesc {
 #[derive(Debug)]
 pub struct ReturnTypeDesc {
     pub ty: Option<ParamType>,
+    /// `PlatformStr` or `PlatformBuf`, written into memory of 1C platform
+    pub platform: bool,
     pub result: bool,
 }
 const META_TYPE_ERR: &str = "expected string literal or path";
@@ -67,6 +74,9 @@ const META_TYPE_ERR: &str = "expected string literal or path";
 pub enum FuncParamType {
     SelfType,
     PlatformType(ParamType),
+    /// `String` or `Blob`, passed as `&U16Str` or `&[u8]`, that borrow
+    /// memory of 1C platform for the duration of the call
+    Borrowed(ParamType),
 }
 
 impl Display for FuncParamType {
@@ -74,6 +84,8 @@ impl Display for FuncParamType {
         let type_str = match self {
             FuncParamType::SelfType => "Self".to_string(),
             FuncParamType::PlatformType(param_type) => format!("{param_type:?}"),
+            FuncParamType::Borrowed(ParamType::Blob) => BLOB_REF_TYPE.to_string(),
+            FuncParamType::Borrowed(_) => STRING_REF_TYPE.to_string(),
         };
         write!(f, "{}", type_str)
     }
@@ -110,10 +122,18 @@ impl TryFrom<&str> for FuncParamType {
         match value {
             BOOL_TYPE => Ok(FuncParamType::PlatformType(ParamType::Bool)),
             I32_TYPE => Ok(FuncParamType::PlatformType(ParamType::I32)),
+            I64_TYPE => Ok(FuncParamType::PlatformType(ParamType::I64)),
+            U64_TYPE => Ok(FuncParamType::PlatformType(ParamType::U64)),
             F64_TYPE => Ok(FuncParamType::PlatformType(ParamType::F64)),
+            DECIMAL_TYPE => Ok(FuncParamType::PlatformType(ParamType::Decimal)),
             STRING_TYPE => Ok(FuncParamType::PlatformType(ParamType::String)),
             DATE_TYPE => Ok(FuncParamType::PlatformType(ParamType::Date)),
+            OPTIONAL_DATE_TYPE => Ok(FuncParamType::PlatformType(ParamType::OptionalDate)),
+            DATE_TZ_TYPE => Ok(FuncParamType::PlatformType(ParamType::DateTz)),
+            DATE_UTC_TYPE => Ok(FuncParamType::PlatformType(ParamType::DateUtc)),
             BLOB_TYPE => Ok(FuncParamType::PlatformType(ParamType::Blob)),
+            STRING_REF_TYPE => Ok(FuncParamType::Borrowed(ParamType::String)),
+            BLOB_REF_TYPE => Ok(FuncParamType::Borrowed(ParamType::Blob)),
             _ => Err(()),
         }
     }
@@ -123,20 +143,34 @@ impl ToTokens for FuncParamType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         *tokens = match self {
             FuncParamType::SelfType => panic!("type not supported for selection"),
-            FuncParamType::PlatformType(param_type) => match param_type {
+            FuncParamType::PlatformType(param_type)
+            | FuncParamType::Borrowed(param_type) => match param_type {
                 ParamType::Bool => {
                     quote! { native_api_1c::native_api_1c_core::interface::ParamValue::Bool }
                 }
                 ParamType::I32 => {
                     quote! { native_api_1c::native_api_1c_core::interface::ParamValue::I32 }
                 }
+                ParamType::I64 => {
+                    quote! { native_api_1c::native_api_1c_core::interface::ParamValue::I64 }
+                }
+                ParamType::U64 => {
+                    quote! { native_api_1c::native_api_1c_core::interface::ParamValue::U64 }
+                }
                 ParamType::F64 => {
                     quote! { native_api_1c::native_api_1c_core::interface::ParamValue::F64 }
                 }
+                // passed as string, see `ParamValue::from_decimal`
+                ParamType::Decimal => {
+                    quote! { native_api_1c::native_api_1c_core::interface::ParamValue::String }
+                }
                 ParamType::String => {
                     quote! { native_api_1c::native_api_1c_core::interface::ParamValue::String }
                 }
-                ParamType::Date => {
+                ParamType::Date
+                | ParamType::OptionalDate
+                | ParamType::DateTz
+                | ParamType::DateUtc => {
                     quote! { native_api_1c::native_api_1c_core::interface::ParamValue::Date }
                 }
                 ParamType::Blob => {
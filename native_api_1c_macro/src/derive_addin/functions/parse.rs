@@ -6,7 +6,7 @@ use syn::{spanned::Spanned, Attribute, DataStruct, Meta};
 use native_api_1c_core::interface::ParamType;
 
 use crate::derive_addin::{
-    parsers::{PropName, ParamTypeWrapper, ParamValueWrapper},
+    parsers::{CoercionWrapper, PropName, ParamValueWrapper, ReturnTypeWrapper},
     utils::ident_option_to_darling_err,
 };
 
@@ -71,6 +71,7 @@ impl FromField for FuncDesc {
             Some(meta) => ReturnTypeDesc::from(meta),
             None => ReturnTypeDesc {
                 ty: None,
+                platform: false,
                 result: false,
             },
         };
@@ -104,6 +105,7 @@ impl FromField for FuncDesc {
                             ty: FuncParamType::SelfType,
                             default: None,
                             optional: None,
+                            coercion: None,
                             out_param: reference.mutability.is_some(),
                             span: first_input.span(),
                         },
@@ -120,8 +122,8 @@ impl FromField for FuncDesc {
 
             name_const: TokenStream::new(),
             name_ru_const: TokenStream::new(),
-            name_slice_const: TokenStream::new(),
-            name_ru_slice_const: TokenStream::new(),
+            name_folded_const: TokenStream::new(),
+            name_ru_folded_const: TokenStream::new(),
 
             params,
             return_value,
@@ -141,6 +143,7 @@ struct FuncArgumentMeta {
     ty: FuncParamType,
     default: Option<Meta>,
     optional: Option<ParamValueWrapper>,
+    coercion: Option<CoercionWrapper>,
     #[allow(dead_code)]
     as_in: Option<()>,
     as_out: Option<()>,
@@ -168,14 +171,38 @@ impl TryFrom<FuncArgumentMeta> for FuncArgumentDesc {
             ));
         }
 
+        if let FuncParamType::Borrowed(_) = arg_meta.ty {
+            if arg_meta.as_out.is_some() {
+                return Err(Self::Error::ConflictingParams(
+                    arg_meta.span.unwrap(),
+                    arg_meta.ty.to_string(),
+                    "as_out".to_string(),
+                ));
+            }
+            if arg_meta.optional.is_some() {
+                return Err(Self::Error::ConflictingParams(
+                    arg_meta.span.unwrap(),
+                    arg_meta.ty.to_string(),
+                    "optional".to_string(),
+                ));
+            }
+        }
+
         let allowed_defaults = match arg_meta.ty.clone() {
             FuncParamType::SelfType => false,
+            FuncParamType::Borrowed(_) => false,
             FuncParamType::PlatformType(ty) => match ty {
                 ParamType::Bool => true,
                 ParamType::I32 => true,
+                ParamType::I64 => true,
+                ParamType::U64 => true,
                 ParamType::F64 => true,
+                ParamType::Decimal => false,
                 ParamType::String => true,
                 ParamType::Date => false,
+                ParamType::OptionalDate => false,
+                ParamType::DateTz => false,
+                ParamType::DateUtc => false,
                 ParamType::Blob => false,
             },
         };
@@ -200,6 +227,7 @@ impl TryFrom<FuncArgumentMeta> for FuncArgumentDesc {
             ty: arg_meta.ty,
             default: default_fixed,
             optional: optional_fixed,
+            coercion: arg_meta.coercion.map(|c| c.0),
             out_param: arg_meta.as_out.is_some(),
             span: arg_meta.span.unwrap(),
         })
@@ -208,14 +236,15 @@ impl TryFrom<FuncArgumentMeta> for FuncArgumentDesc {
 
 #[derive(FromMeta, Debug)]
 struct FuncReturnMeta {
-    ty: Option<ParamTypeWrapper>,
+    ty: Option<ReturnTypeWrapper>,
     result: Option<()>,
 }
 
 impl From<FuncReturnMeta> for ReturnTypeDesc {
     fn from(arg_meta: FuncReturnMeta) -> Self {
         Self {
-            ty: arg_meta.ty.map(|p| p.0),
+            platform: arg_meta.ty.as_ref().is_some_and(|p| p.platform),
+            ty: arg_meta.ty.map(|p| p.ty),
             result: arg_meta.result.is_some(),
         }
     }
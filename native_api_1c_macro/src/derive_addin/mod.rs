@@ -1,8 +1,11 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Ident};
+use darling::FromMeta;
+use native_api_1c_core::interface::Coercion;
+use syn::{parse_macro_input, spanned::Spanned, DeriveInput, Ident};
 
 use functions::{collectors::*, parse::parse_functions};
+use parsers::CoercionWrapper;
 use props::{collectors::*, parse::parse_props};
 use utils::{macros::tkn_err, str_literal_token};
 
@@ -21,32 +24,53 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 }
 
 fn derive_result(input: &DeriveInput) -> Result<TokenStream, TokenStream> {
-    let impl_block = build_impl_block(input).map_err(|darling_error| {
-        let error_tokens = darling_error.write_errors();
-        let error_tokens = quote! {
-            compile_error!(#error_tokens);
-        };
-        error_tokens
-    })?;
+    let impl_block = build_impl_block(input).map_err(|darling_error| darling_error.write_errors())?;
 
     Ok(quote! {
         #impl_block
     })
 }
 
-fn get_addin_name_from_attribute( input: &DeriveInput ) -> Result<Option<TokenStream>, syn::Error> {
+/// Options of the AddIn, set with `#[add_in(...)]` attribute on the struct
+#[derive(Default)]
+struct AddInAttrs {
+    /// Name of the AddIn, as it is registered in 1C
+    name: Option<TokenStream>,
+    /// Event buffer depth, set in `init` if 1C platform buffer is smaller
+    event_buffer_depth: Option<syn::Expr>,
+    /// Coercion of arguments and property values, that do not set their own
+    coercion: Option<Coercion>,
+    /// Variant of `ApiVersion`, reported with `GetInfo`
+    api_version: Option<Ident>,
+    /// Variant of `PStrEncoding`, used for single-byte strings
+    pstr_encoding: Option<Ident>,
+}
+
+fn parse_add_in_attrs(input: &DeriveInput) -> Result<AddInAttrs, syn::Error> {
+    let mut add_in_attrs = AddInAttrs::default();
 
     for attr in &input.attrs {
         if attr.path().is_ident("add_in") {
-            let nested = match attr.parse_args_with(syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated) {
-                Ok(r) => r,
-                Err(e) => return Err(e),
-            };
+            let nested = attr.parse_args_with(syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)?;
 
             for meta in nested {
                 match &meta {
                     syn::Meta::NameValue(syn::MetaNameValue{path, value, ..}) if path.is_ident("name") => {
-                        return Ok(Some( quote!{ #value } ));
+                        add_in_attrs.name.get_or_insert_with(|| quote!{ #value });
+                    },
+                    syn::Meta::NameValue(syn::MetaNameValue{path, value, ..}) if path.is_ident("event_buffer_depth") => {
+                        add_in_attrs.event_buffer_depth = Some(value.clone());
+                    },
+                    syn::Meta::NameValue(syn::MetaNameValue{path, value, ..}) if path.is_ident("coercion") => {
+                        let coercion = CoercionWrapper::from_expr(value)
+                            .map_err(|err| syn::Error::new(value.span(), err))?;
+                        add_in_attrs.coercion = Some(coercion.0);
+                    },
+                    syn::Meta::NameValue(syn::MetaNameValue{path, value, ..}) if path.is_ident("api_version") => {
+                        add_in_attrs.api_version = Some(variant_ident(value, &API_VERSIONS, "api version")?);
+                    },
+                    syn::Meta::NameValue(syn::MetaNameValue{path, value, ..}) if path.is_ident("pstr_encoding") => {
+                        add_in_attrs.pstr_encoding = Some(variant_ident(value, &PSTR_ENCODINGS, "pstr encoding")?);
                     },
                     _ => {},
                 }
@@ -54,7 +78,45 @@ fn get_addin_name_from_attribute( input: &DeriveInput ) -> Result<Option<TokenSt
         }
     };
 
-    Ok(None)
+    Ok(add_in_attrs)
+}
+
+const API_VERSIONS: [&str; 2] = ["V1", "V2"];
+const PSTR_ENCODINGS: [&str; 2] = ["Utf8", "Windows1251"];
+
+/// Parses variant of a core enum, given as a bare identifier, e.g. `V1`
+fn variant_ident(expr: &syn::Expr, variants: &[&str], what: &str) -> Result<Ident, syn::Error> {
+    let ident = match expr {
+        syn::Expr::Path(path) => path.path.get_ident().cloned(),
+        _ => None,
+    };
+    match ident {
+        Some(ident) if variants.iter().any(|variant| ident == variant) => Ok(ident),
+        _ => Err(syn::Error::new(
+            expr.span(),
+            format!("unknown {what}. Must be one of: {}", variants.join(", ")),
+        )),
+    }
+}
+
+fn get_mem_manager_field(struct_data: &syn::DataStruct) -> Result<Option<&Ident>, darling::Error> {
+    let mut mem_manager_fields = struct_data
+        .fields
+        .iter()
+        .filter(|field| field.attrs.iter().any(|attr| attr.path().is_ident("add_in_mem")));
+
+    let Some(field) = mem_manager_fields.next() else {
+        return Ok(None);
+    };
+    if let Some(extra_field) = mem_manager_fields.next() {
+        return tkn_err!("Only one field can have `add_in_mem` attribute", &extra_field.span());
+    }
+
+    field
+        .ident
+        .as_ref()
+        .map(Some)
+        .ok_or_else(|| darling::Error::custom("`add_in_mem` field must be named").with_span(&field.span()))
 }
 
 fn build_impl_block(input: &DeriveInput) -> Result<proc_macro2::TokenStream, darling::Error> {
@@ -66,15 +128,51 @@ fn build_impl_block(input: &DeriveInput) -> Result<proc_macro2::TokenStream, dar
         );
     };
 
-    let addin_name = if let Some(addin_name) = get_addin_name_from_attribute(input)? {
+    let add_in_attrs = parse_add_in_attrs(input)?;
+
+    let addin_name = if let Some(addin_name) = add_in_attrs.name {
         addin_name
     } else {
         str_literal_token(&struct_ident.to_string(), struct_ident)?
     };
 
+    let set_event_buffer_depth = match add_in_attrs.event_buffer_depth {
+        Some(depth) => quote! {
+            if interface.get_event_buffer_depth() < (#depth) {
+                interface.set_event_buffer_depth(#depth);
+            }
+        },
+        None => quote! {},
+    };
+
+    let api_version = add_in_attrs
+        .api_version
+        .unwrap_or_else(|| Ident::new("V2", struct_ident.span()));
+    let pstr_encoding = add_in_attrs
+        .pstr_encoding
+        .unwrap_or_else(|| Ident::new("Utf8", struct_ident.span()));
+
+    let set_mem_manager_definition = match get_mem_manager_field(struct_data)? {
+        Some(mem_manager_field) => quote! {
+            fn set_mem_manager(&mut self, mem: &'static native_api_1c::native_api_1c_core::ffi::memory_manager::MemoryManager) -> bool {
+                self.#mem_manager_field = Some(mem).into();
+                true
+            }
+        },
+        None => quote! {},
+    };
+
     let mut props = parse_props(struct_data)?;
     let mut functions = parse_functions(struct_data)?;
 
+    let coercion = add_in_attrs.coercion.unwrap_or_default();
+    for prop in &mut props {
+        prop.coercion.get_or_insert(coercion);
+    }
+    for param in functions.iter_mut().flat_map(|func| &mut func.params) {
+        param.coercion.get_or_insert(coercion);
+    }
+
     let addin_name_const = Ident::new("ADDIN_NAME", struct_ident.span());
     let addin_consts = quote! {
         const #addin_name_const: &'static native_api_1c::native_api_1c_core::widestring::U16CStr = const { native_api_1c::native_api_1c_core::widestring::u16cstr!(#addin_name) };
@@ -118,14 +216,22 @@ fn build_impl_block(input: &DeriveInput) -> Result<proc_macro2::TokenStream, dar
             #func_consts
         }
 
+        #[allow(clippy::useless_conversion, clippy::clone_on_copy)]
         impl native_api_1c::native_api_1c_core::interface::AddInWrapper for #struct_ident {
             fn init(&mut self, interface: &'static native_api_1c::native_api_1c_core::ffi::connection::Connection) -> bool {
+                #set_event_buffer_depth
                 self.connection = std::sync::Arc::new(Some(interface));
                 true
             }
 
+            #set_mem_manager_definition
+
             fn get_info(&self) -> u16 {
-                2000
+                native_api_1c::native_api_1c_core::ffi::api_version::ApiVersion::#api_version.info()
+            }
+
+            fn pstr_encoding(&self) -> native_api_1c::native_api_1c_core::ffi::api_version::PStrEncoding {
+                native_api_1c::native_api_1c_core::ffi::api_version::PStrEncoding::#pstr_encoding
             }
 
             fn done(&mut self) {}
@@ -1,9 +1,12 @@
 use darling::FromMeta;
-use proc_macro2::TokenStream;
-use quote::ToTokens;
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
 
-use super::constants::{BLOB_TYPE, BOOL_TYPE, DATE_TYPE, F64_TYPE, I32_TYPE, STRING_TYPE};
-use native_api_1c_core::interface::ParamType;
+use super::constants::{
+    BLOB_BUF_TYPE, BLOB_TYPE, BOOL_TYPE, DATE_TYPE, DATE_TZ_TYPE, DATE_UTC_TYPE, DECIMAL_TYPE,
+    F64_TYPE, I32_TYPE, I64_TYPE, OPTIONAL_DATE_TYPE, STRING_BUF_TYPE, STRING_TYPE, U64_TYPE,
+};
+use native_api_1c_core::interface::{Coercion, ParamType};
 
 const META_TYPE_ERR: &str = "expected string literal or path";
 const META_TYPED_VALUE_ERR: &str = "expected typed value";
@@ -42,15 +45,60 @@ impl TryFrom<&str> for ParamTypeWrapper {
         match value {
             BOOL_TYPE => Ok(ParamTypeWrapper(ParamType::Bool)),
             I32_TYPE => Ok(ParamTypeWrapper(ParamType::I32)),
+            I64_TYPE => Ok(ParamTypeWrapper(ParamType::I64)),
+            U64_TYPE => Ok(ParamTypeWrapper(ParamType::U64)),
             F64_TYPE => Ok(ParamTypeWrapper(ParamType::F64)),
+            DECIMAL_TYPE => Ok(ParamTypeWrapper(ParamType::Decimal)),
             STRING_TYPE => Ok(ParamTypeWrapper(ParamType::String)),
             DATE_TYPE => Ok(ParamTypeWrapper(ParamType::Date)),
+            OPTIONAL_DATE_TYPE => Ok(ParamTypeWrapper(ParamType::OptionalDate)),
+            DATE_TZ_TYPE => Ok(ParamTypeWrapper(ParamType::DateTz)),
+            DATE_UTC_TYPE => Ok(ParamTypeWrapper(ParamType::DateUtc)),
             BLOB_TYPE => Ok(ParamTypeWrapper(ParamType::Blob)),
             _ => Err(()),
         }
     }
 }
 
+/// Type of returned value, `platform` is set for `StrBuf` and `BlobBuf`, that
+/// are written into memory of 1C platform by the function itself
+#[derive(Debug)]
+pub struct ReturnTypeWrapper {
+    pub ty: ParamType,
+    pub platform: bool,
+}
+
+impl FromMeta for ReturnTypeWrapper {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let meta_type_err = darling::Error::custom(META_TYPE_ERR);
+        let expr_string = match expr {
+            syn::Expr::Lit(str_lit) => match str_lit.lit {
+                syn::Lit::Str(ref str) => str.value(),
+                _ => return Err(meta_type_err),
+            },
+            syn::Expr::Path(path) => path.path.segments.first().unwrap().ident.to_string(),
+            _ => return Err(meta_type_err),
+        };
+        Self::from_string(&expr_string)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            STRING_BUF_TYPE => Ok(Self { ty: ParamType::String, platform: true }),
+            BLOB_BUF_TYPE => Ok(Self { ty: ParamType::Blob, platform: true }),
+            _ => ParamTypeWrapper::try_from(value)
+                .map(|ty| Self { ty: ty.0, platform: false })
+                .map_err(|_| {
+                    let joined_allowed_types =
+                        crate::derive_addin::constants::ALL_RETURN_TYPES.join(", ");
+                    darling::Error::custom(format!(
+                        "unknown type `{value}`. Must be one of: {joined_allowed_types}",
+                    ))
+                }),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum PropName {
     StringLiteral(syn::LitStr),
@@ -110,7 +158,77 @@ impl FromMeta for ParamValueWrapper {
                     value: value.to_token_stream(),
                 })
             },
-            _ => return Err(meta_type_err),
+            _ => Err(meta_type_err),
         }
     }
 }
+
+const COERCIONS: [(&str, Coercion); 3] = [
+    ("Strict", Coercion::Strict),
+    ("Numeric", Coercion::Numeric),
+    ("Strings", Coercion::Strings),
+];
+
+/// Value of `coercion` option, see `Coercion` for available rules
+#[derive(Clone, Copy, Debug)]
+pub struct CoercionWrapper(pub Coercion);
+
+impl FromMeta for CoercionWrapper {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let meta_type_err = darling::Error::custom(META_TYPE_ERR).with_span(expr);
+        let expr_string = match expr {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(str_lit), .. }) => str_lit.value(),
+            syn::Expr::Path(path) => match path.path.get_ident() {
+                Some(ident) => ident.to_string(),
+                None => return Err(meta_type_err),
+            },
+            _ => return Err(meta_type_err),
+        };
+        Self::from_string(&expr_string).map_err(|err| err.with_span(expr))
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        COERCIONS
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, coercion)| CoercionWrapper(*coercion))
+            .ok_or_else(|| {
+                let joined_allowed = COERCIONS.map(|(name, _)| name).join(", ");
+                darling::Error::custom(format!(
+                    "unknown coercion `{value}`. Must be one of: {joined_allowed}",
+                ))
+            })
+    }
+}
+
+/// Arguments of `ParamValue::coerce_to` for conversion to `ty`, `None` if the value is used as is
+pub fn coerce_args(ty: ParamType, coercion: Coercion) -> Option<TokenStream> {
+    let coercible = matches!(
+        ty,
+        ParamType::Bool | ParamType::I32 | ParamType::I64 | ParamType::U64 | ParamType::F64
+    );
+    if !coercible || coercion == Coercion::Strict {
+        return None;
+    }
+
+    let ty = syn::Ident::new(&format!("{ty:?}"), proc_macro2::Span::call_site());
+    let coercion = syn::Ident::new(&format!("{coercion:?}"), proc_macro2::Span::call_site());
+    Some(quote! {
+        native_api_1c::native_api_1c_core::interface::ParamType::#ty,
+        native_api_1c::native_api_1c_core::interface::Coercion::#coercion
+    })
+}
+
+/// Conversion of a date, taken from `ParamValue`, to the type of argument or property,
+/// `None` if `ty` is not a date. Date may be out of range of the target type,
+/// e.g. `chrono::NaiveDateTime`, so conversion fails with `error` and the reason as its source
+pub fn date_conversion(ty: ParamType, error: TokenStream, span: Span) -> Option<TokenStream> {
+    if !matches!(ty, ParamType::Date | ParamType::DateTz | ParamType::DateUtc) {
+        return None;
+    }
+
+    Some(quote_spanned! { span =>
+        .try_into()
+        .map_err(|err| #error.with_source(err))?
+    })
+}
@@ -22,19 +22,26 @@ impl<'a> FromIterator<(usize, &'a PropDesc)> for FindPropCollector {
         let mut find_prop_body = TokenStream::new();
 
         for (prop_index, prop_desc) in iter {
-            let name_slice_const = &prop_desc.name_slice_const;
-            let name_ru_slice_const = &prop_desc.name_ru_slice_const;
+            let name_folded_const = &prop_desc.name_folded_const;
+            let name_ru_folded_const = &prop_desc.name_ru_folded_const;
 
             find_prop_body.extend(quote_spanned! { prop_desc.ident.span() =>
-                Self::#name_slice_const | Self::#name_ru_slice_const => { 
+                Self::#name_folded_const | Self::#name_ru_folded_const => { 
                     Some(#prop_index) 
                 },
             });
         }
 
         let _definition = quote! {
+            // `name` and `name_ru` of the same item may be equal
+            #[allow(unreachable_patterns)]
             fn find_prop(&self, name: &native_api_1c::native_api_1c_core::widestring::U16CStr) -> Option<usize> {
-                match name.as_slice_with_nul() {
+                // names are checked for collisions at compile time
+                #[allow(clippy::let_unit_value)]
+                let () = Self::ADDIN_PROP_NAMES_UNIQUE;
+                // 1C platform looks names up ignoring case
+                let name = native_api_1c::native_api_1c_core::interface::FoldedName::new(name.as_slice_with_nul());
+                match name.as_slice() {
                     #find_prop_body
                     _ => {
                         None
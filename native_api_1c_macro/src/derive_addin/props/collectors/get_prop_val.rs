@@ -46,7 +46,7 @@ impl<'a> FromIterator<(usize, &'a PropDesc)> for GetPropValCollector {
                 match num {
                     #body
                     _ => {
-                        Err(())
+                        Err(native_api_1c::native_api_1c_core::interface::AddInError::prop_not_found(num))
                     }
                 }
             }
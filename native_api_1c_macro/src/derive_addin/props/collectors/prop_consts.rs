@@ -1,8 +1,8 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{quote_spanned, ToTokens};
 use syn::Ident;
 
-use crate::derive_addin::props::PropDesc;
+use crate::derive_addin::{props::PropDesc, utils::{unique_names_check, ItemName}};
 
 use super::{empty_prop_collector_error, PropCollectorMut};
 
@@ -21,6 +21,8 @@ impl Default for PropConstantsCollector {
 impl<'a> FromIterator<(usize, &'a mut PropDesc)> for PropConstantsCollector {
     fn from_iter<T: IntoIterator<Item = (usize, &'a mut PropDesc)>>(iter: T) -> Self {
         let mut body = TokenStream::new();
+        // every name, to check that names differ
+        let mut names = Vec::new();
 
         for (prop_index, prop_desc) in iter {
             let name_literal = &prop_desc.name_literal;
@@ -28,25 +30,52 @@ impl<'a> FromIterator<(usize, &'a mut PropDesc)> for PropConstantsCollector {
 
             let name_const = Ident::new(&format!("ADDIN_PROP_NAME_{}", prop_index + 1), prop_desc.ident.span());
             let name_ru_const = Ident::new(&format!("ADDIN_PROP_NAME_RU_{}", prop_index + 1), prop_desc.ident.span());
-            let name_slice_const = Ident::new(&format!("ADDIN_PROP_NAME_{}_SLICE", prop_index + 1), prop_desc.ident.span());
-            let name_ru_slice_const = Ident::new(&format!("ADDIN_PROP_NAME_RU_{}_SLICE", prop_index + 1), prop_desc.ident.span());
+            let name_folded_const = Ident::new(&format!("ADDIN_PROP_NAME_{}_FOLDED", prop_index + 1), prop_desc.ident.span());
+            let name_ru_folded_const = Ident::new(&format!("ADDIN_PROP_NAME_RU_{}_FOLDED", prop_index + 1), prop_desc.ident.span());
 
             body.extend(quote_spanned! { prop_desc.ident.span() =>
                 const #name_const: &'static native_api_1c::native_api_1c_core::widestring::U16CStr = const { native_api_1c::native_api_1c_core::widestring::u16cstr!(#name_literal) };
                 const #name_ru_const: &'static native_api_1c::native_api_1c_core::widestring::U16CStr = const { native_api_1c::native_api_1c_core::widestring::u16cstr!(#name_ru_literal) };
-                const #name_slice_const: &'static [native_api_1c::native_api_1c_core::widestring::internals::core::primitive::u16] = const { Self::#name_const.as_slice_with_nul() };
-                const #name_ru_slice_const: &'static [native_api_1c::native_api_1c_core::widestring::internals::core::primitive::u16] = const { Self::#name_ru_const.as_slice_with_nul() };
+            });
+            // folded at compile time, `Self` can not be used in array length
+            body.extend(quote_spanned! { prop_desc.ident.span() =>
+                const #name_folded_const: &'static [native_api_1c::native_api_1c_core::widestring::internals::core::primitive::u16] = const {
+                    const NAME: &[native_api_1c::native_api_1c_core::widestring::internals::core::primitive::u16] = native_api_1c::native_api_1c_core::widestring::u16cstr!(#name_literal).as_slice_with_nul();
+                    const FOLDED: [native_api_1c::native_api_1c_core::widestring::internals::core::primitive::u16; NAME.len()] = native_api_1c::native_api_1c_core::interface::fold_name(NAME);
+                    &FOLDED
+                };
+                const #name_ru_folded_const: &'static [native_api_1c::native_api_1c_core::widestring::internals::core::primitive::u16] = const {
+                    const NAME: &[native_api_1c::native_api_1c_core::widestring::internals::core::primitive::u16] = native_api_1c::native_api_1c_core::widestring::u16cstr!(#name_ru_literal).as_slice_with_nul();
+                    const FOLDED: [native_api_1c::native_api_1c_core::widestring::internals::core::primitive::u16; NAME.len()] = native_api_1c::native_api_1c_core::interface::fold_name(NAME);
+                    &FOLDED
+                };
+            });
+            names.push(ItemName {
+                index: prop_index,
+                field: prop_desc.ident.clone(),
+                name: name_literal.clone(),
+                folded_const: name_folded_const.clone(),
+            });
+            names.push(ItemName {
+                index: prop_index,
+                field: prop_desc.ident.clone(),
+                name: name_ru_literal.clone(),
+                folded_const: name_ru_folded_const.clone(),
             });
 
             prop_desc.name_const = name_const.into_token_stream();
             prop_desc.name_ru_const = name_ru_const.into_token_stream();
-            prop_desc.name_slice_const = name_slice_const.into_token_stream();
-            prop_desc.name_ru_slice_const = name_ru_slice_const.into_token_stream();
+            prop_desc.name_folded_const = name_folded_const.into_token_stream();
+            prop_desc.name_ru_folded_const = name_ru_folded_const.into_token_stream();
         }
 
-        Self {
-            generated: Ok(body),
-        }
+        let unique_const = Ident::new("ADDIN_PROP_NAMES_UNIQUE", Span::call_site());
+        let generated = unique_names_check(&names, "properties", &unique_const).map(|unique_check| {
+            body.extend(unique_check);
+            body
+        });
+
+        Self { generated }
     }
 }
 
@@ -3,7 +3,10 @@ use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::Ident;
 
-use crate::derive_addin::{props::PropDesc};
+use crate::derive_addin::{
+    parsers::{coerce_args, date_conversion},
+    props::PropDesc,
+};
 
 use super::{empty_prop_collector_error, PropCollector};
 
@@ -31,11 +34,23 @@ impl<'a> FromIterator<(usize, &'a PropDesc)> for SetPropValCollector {
             let prop_ident = &prop_desc.ident;
             let into_type_fn = Ident::new(ParamValue::into_type_fn_name(prop_desc.ty), prop_desc.ident.span());
 
+            let convert = date_conversion(
+                prop_desc.ty,
+                quote! { native_api_1c::native_api_1c_core::interface::AddInError::invalid_prop_value(num) },
+                prop_desc.ident.span(),
+            )
+            .unwrap_or_else(|| quote_spanned! { prop_desc.ident.span() => .into() });
+
+            let val = match coerce_args(prop_desc.ty, prop_desc.coercion.unwrap_or_default()) {
+                Some(coerce_args) => quote_spanned! { prop_desc.ident.span() => val.coerce_into(#coerce_args) },
+                None => quote_spanned! { prop_desc.ident.span() => val },
+            };
+
             body.extend(quote_spanned! { prop_desc.ident.span() =>
                 #prop_index => {
-                    self.#prop_ident = native_api_1c::native_api_1c_core::interface::ParamValue::#into_type_fn(val)
-                        .ok_or(())?
-                        .into();
+                    self.#prop_ident = native_api_1c::native_api_1c_core::interface::ParamValue::#into_type_fn(#val)
+                        .ok_or_else(|| native_api_1c::native_api_1c_core::interface::AddInError::invalid_prop_value(num))?
+                        #convert;
                     Ok(())
                 },
             });
@@ -50,7 +65,7 @@ impl<'a> FromIterator<(usize, &'a PropDesc)> for SetPropValCollector {
                 match num {
                     #body
                     _ => {
-                        Err(())
+                        Err(native_api_1c::native_api_1c_core::interface::AddInError::prop_not_found(num))
                     }
                 }
             }
@@ -1,6 +1,6 @@
 use proc_macro2::{Ident, TokenStream};
 
-use native_api_1c_core::interface::ParamType;
+use native_api_1c_core::interface::{Coercion, ParamType};
 
 pub mod collectors;
 pub mod generate;
@@ -15,10 +15,12 @@ pub struct PropDesc {
 
     pub name_const: TokenStream,
     pub name_ru_const: TokenStream,
-    pub name_slice_const: TokenStream,
-    pub name_ru_slice_const: TokenStream,
+    pub name_folded_const: TokenStream,
+    pub name_ru_folded_const: TokenStream,
 
     pub readable: bool,
     pub writable: bool,
     pub ty: ParamType,
+    /// Coercion of written value, component coercion is used if not set
+    pub coercion: Option<Coercion>,
 }
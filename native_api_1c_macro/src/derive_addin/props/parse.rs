@@ -2,7 +2,10 @@ use darling::{FromField, FromMeta};
 use proc_macro2::TokenStream;
 use syn::{Attribute, DataStruct};
 
-use crate::derive_addin::{parsers::PropName, utils::ident_option_to_darling_err};
+use crate::derive_addin::{
+    parsers::{CoercionWrapper, PropName},
+    utils::ident_option_to_darling_err,
+};
 
 use super::PropDesc;
 use super::super::parsers::ParamTypeWrapper;
@@ -39,12 +42,13 @@ impl FromField for PropDesc {
 
             name_const: TokenStream::new(),
             name_ru_const: TokenStream::new(),
-            name_slice_const: TokenStream::new(),
-            name_ru_slice_const: TokenStream::new(),
+            name_folded_const: TokenStream::new(),
+            name_ru_folded_const: TokenStream::new(),
 
             readable: prop_meta.readable.is_some(),
             writable: prop_meta.writable.is_some(),
             ty: prop_meta.ty.0,
+            coercion: prop_meta.coercion.map(|c| c.0),
         })
     }
 }
@@ -56,6 +60,7 @@ pub struct PropMeta {
     pub name_ru: PropName,
     pub readable: Option<()>,
     pub writable: Option<()>,
+    pub coercion: Option<CoercionWrapper>,
 }
 
 pub fn parse_props(struct_data: &DataStruct) -> Result<Vec<PropDesc>, darling::Error> {
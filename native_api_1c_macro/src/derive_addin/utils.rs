@@ -1,3 +1,4 @@
+use native_api_1c_core::interface::eq_ignore_case;
 use proc_macro2::LexError;
 use syn::{spanned::Spanned, Ident};
 
@@ -41,3 +42,69 @@ where
                 .with_span(err_ident)
         })
 }
+
+/// Name of a method or a property, checked by [`unique_names_check`]
+pub struct ItemName {
+    pub index: usize,
+    pub field: Ident,
+    /// name as it is written in the attribute: string literal or path
+    pub name: proc_macro2::TokenStream,
+    /// const with the name, folded at compile time
+    pub folded_const: Ident,
+}
+
+/// Checks, that names of different items differ ignoring case, as 1C platform
+/// looks names up this way. Names of the same item may be equal.
+/// String literals are compared here, and a collision is reported at the
+/// field, declared later. Names, given by paths to constants, are only known
+/// at compile time, so they are checked by assertions in `unique_const`
+pub fn unique_names_check(
+    names: &[ItemName],
+    items: &str,
+    unique_const: &Ident,
+) -> Result<proc_macro2::TokenStream, darling::Error> {
+    let mut errors = Vec::new();
+    // items, that collide, are reported once, even if several names collide
+    let mut collisions = Vec::new();
+    let mut checks = proc_macro2::TokenStream::new();
+    for (i, first) in names.iter().enumerate() {
+        for second in &names[i + 1..] {
+            if first.index == second.index || collisions.contains(&(first.index, second.index)) {
+                continue;
+            }
+            let message = format!(
+                "{items} `{}` and `{}` have the same name, ignoring case",
+                first.field, second.field
+            );
+
+            let literal = |name: &proc_macro2::TokenStream| syn::parse2::<syn::LitStr>(name.clone()).ok();
+            match (literal(&first.name), literal(&second.name)) {
+                (Some(first_name), Some(second_name)) => {
+                    let first_name: Vec<u16> = first_name.value().encode_utf16().collect();
+                    let second_name: Vec<u16> = second_name.value().encode_utf16().collect();
+                    if eq_ignore_case(&first_name, &second_name) {
+                        collisions.push((first.index, second.index));
+                        errors.push(syn::Error::new(second.field.span(), message).into());
+                    }
+                }
+                _ => {
+                    let first_const = &first.folded_const;
+                    let second_const = &second.folded_const;
+                    checks.extend(quote::quote! {
+                        assert!(
+                            !native_api_1c::native_api_1c_core::interface::eq_ignore_case(Self::#first_const, Self::#second_const),
+                            #message
+                        );
+                    });
+                }
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(darling::Error::multiple(errors));
+    }
+    Ok(quote::quote! {
+        const #unique_const: () = { #checks };
+    })
+}
@@ -58,20 +58,44 @@ pub fn extern_functions(input: proc_macro::TokenStream) -> proc_macro::TokenStre
         const #class_names_const: &'static native_api_1c::native_api_1c_core::widestring::U16CStr = const { native_api_1c::native_api_1c_core::widestring::u16cstr!(#names_lit) };
     };
 
+    let capabilities = match &extern_add_ins.library.capabilities {
+        Some(capabilities) => quote! {
+            native_api_1c::native_api_1c_core::ffi::AppCapabilities::#capabilities
+        },
+        None => quote! {
+            native_api_1c::native_api_1c_core::ffi::AppCapabilities::LATEST
+        },
+    };
+
+    let attach_type = match library_attach_type(&extern_add_ins) {
+        Ok(attach_type) => attach_type,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
     let result = quote! {
-        pub static mut PLATFORM_CAPABILITIES: std::sync::atomic::AtomicI32 =
-            std::sync::atomic::AtomicI32::new(-1);
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub extern "C" fn SetPlatformCapabilities(
+            capabilities: std::ffi::c_int,
+        ) -> native_api_1c::native_api_1c_core::ffi::AppCapabilities {
+            native_api_1c::native_api_1c_core::ffi::set_platform_capabilities(
+                capabilities,
+                #capabilities,
+            )
+        }
 
         #[allow(non_snake_case)]
         #[no_mangle]
         pub extern "C" fn GetAttachType() -> native_api_1c::native_api_1c_core::ffi::AttachType {
-            native_api_1c::native_api_1c_core::ffi::AttachType::Any
+            native_api_1c::native_api_1c_core::ffi::AttachType::#attach_type
         }
 
         #[allow(non_snake_case)]
         #[no_mangle]
         pub unsafe extern "C" fn DestroyObject(component: *mut *mut std::ffi::c_void) -> std::ffi::c_long {
-            native_api_1c::native_api_1c_core::ffi::destroy_component(component)
+            native_api_1c::native_api_1c_core::ffi::panic_guard::guard(None, "DestroyObject", -1, || {
+                native_api_1c::native_api_1c_core::ffi::destroy_component(component)
+            })
         }
 
         #[allow(non_snake_case)]
@@ -80,7 +104,9 @@ pub fn extern_functions(input: proc_macro::TokenStream) -> proc_macro::TokenStre
             name: *const u16,
             component: *mut *mut std::ffi::c_void,
         ) -> std::ffi::c_long {
-            #get_class_object_body
+            native_api_1c::native_api_1c_core::ffi::panic_guard::guard(None, "GetClassObject", 0, || {
+                #get_class_object_body
+            })
         }
 
         #[allow(non_snake_case)]
@@ -93,3 +119,41 @@ pub fn extern_functions(input: proc_macro::TokenStream) -> proc_macro::TokenStre
 
     result.into()
 }
+
+/// Combines attach types, required by the library and its components:
+/// `Any` allows everything, while `Isolated` and `NotIsolated` exclude each other
+fn library_attach_type(extern_add_ins: &ExternAddInsDesc) -> syn::Result<Ident> {
+    let required = extern_add_ins
+        .library
+        .attach_type
+        .iter()
+        .chain(
+            extern_add_ins
+                .components
+                .iter()
+                .filter_map(|component| component.attach_type.as_ref()),
+        )
+        .filter(|attach_type| *attach_type != "Any");
+
+    let mut result: Option<&Ident> = None;
+    for attach_type in required {
+        match result {
+            Some(previous) if previous != attach_type => {
+                let mut err = syn::Error::new(
+                    attach_type.span(),
+                    format!("attach type `{attach_type}` conflicts with `{previous}`, required elsewhere in the library"),
+                );
+                err.combine(syn::Error::new(
+                    previous.span(),
+                    format!("`{previous}` is required here"),
+                ));
+                return Err(err);
+            }
+            _ => result = Some(attach_type),
+        }
+    }
+
+    Ok(result
+        .cloned()
+        .unwrap_or_else(|| Ident::new("Any", Span::call_site())))
+}
@@ -6,16 +6,18 @@ use syn::{
     punctuated::Punctuated,
     spanned::Spanned,
     token::Comma,
-    Attribute,
+    Attribute, Expr, ExprPath, Ident, Meta, MetaNameValue,
 };
 
 #[derive(Debug)]
 pub struct ExternAddInsDesc {
+    pub library: ExternAddInLibraryDesc,
     pub components: Vec<ExternAddInComponentDesc>,
 }
 
 impl Parse for ExternAddInsDesc {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let library = ExternAddInLibraryDesc::parse(input)?;
         let parser = Punctuated::<syn::Expr, Comma>::parse_terminated(input).unwrap();
 
         let components = parser
@@ -26,7 +28,85 @@ impl Parse for ExternAddInsDesc {
             })
             .collect::<syn::Result<Vec<_>>>()?;
 
-        Ok(ExternAddInsDesc { components })
+        Ok(ExternAddInsDesc {
+            library,
+            components,
+        })
+    }
+}
+
+/// Options of the whole library, set with `#![add_in_library(...)]` inner
+/// attribute at the beginning of `extern_functions!`
+#[derive(Debug, Default)]
+pub struct ExternAddInLibraryDesc {
+    /// Variant of `AppCapabilities`, supported by the library
+    pub capabilities: Option<Ident>,
+    /// Variant of `AttachType`, allowed for all components of the library
+    pub attach_type: Option<Ident>,
+}
+
+impl Parse for ExternAddInLibraryDesc {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_inner)?;
+        let mut library_desc = ExternAddInLibraryDesc::default();
+
+        for attr in &attrs {
+            if !attr.path().is_ident("add_in_library") {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "only `add_in_library` attribute is allowed here",
+                ));
+            }
+
+            let nested = attr.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)?;
+            for meta in nested {
+                let Meta::NameValue(MetaNameValue { path, value, .. }) = &meta else {
+                    return Err(syn::Error::new(meta.span(), "expected `option = value`"));
+                };
+
+                if path.is_ident("capabilities") {
+                    let capabilities = expr_ident(value)?;
+                    if capabilities == "Invalid" {
+                        return Err(syn::Error::new(
+                            capabilities.span(),
+                            "expected one of `V1`, `V2`, `V3`",
+                        ));
+                    }
+                    library_desc.capabilities = Some(capabilities);
+                } else if path.is_ident("attach_type") {
+                    library_desc.attach_type = Some(attach_type_ident(value)?);
+                } else {
+                    return Err(syn::Error::new(
+                        path.span(),
+                        "unknown `add_in_library` option",
+                    ));
+                }
+            }
+        }
+
+        Ok(library_desc)
+    }
+}
+
+const ATTACH_TYPES: [&str; 3] = ["NotIsolated", "Isolated", "Any"];
+
+fn attach_type_ident(expr: &Expr) -> syn::Result<Ident> {
+    let attach_type = expr_ident(expr)?;
+    if !ATTACH_TYPES.iter().any(|variant| attach_type == variant) {
+        return Err(syn::Error::new(
+            attach_type.span(),
+            "expected one of `NotIsolated`, `Isolated`, `Any`",
+        ));
+    }
+    Ok(attach_type)
+}
+
+fn expr_ident(expr: &Expr) -> syn::Result<Ident> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) if path.get_ident().is_some() => {
+            Ok(path.get_ident().unwrap().clone())
+        }
+        _ => Err(syn::Error::new(expr.span(), "expected identifier")),
     }
 }
 
@@ -34,12 +114,15 @@ impl Parse for ExternAddInsDesc {
 struct ExternAddInComponentMeta {
     #[darling(rename = "name")]
     name_override: Option<String>,
+    attach_type: Option<Expr>,
 }
 
 #[derive(Debug)]
 pub struct ExternAddInComponentDesc {
     #[allow(dead_code)]
     pub name_override: Option<String>,
+    /// Variant of `AttachType`, required by the component
+    pub attach_type: Option<Ident>,
     pub init_tkn: TokenStream,
 }
 
@@ -62,17 +145,23 @@ impl Parse for ExternAddInComponentDesc {
             Some(Ok(desc)) => Some(desc),
             Some(Err(err)) => {
                 return Err(syn::Error::new(
-                    add_in_component_attrs[1].span(),
+                    add_in_component_attrs[0].span(),
                     err.to_string(),
                 ))
             }
             None => None,
         };
+        let attach_type = addin_desc
+            .as_ref()
+            .and_then(|desc| desc.attach_type.as_ref())
+            .map(attach_type_ident)
+            .transpose()?;
 
         let init_tkn = input.call(TokenStream::parse).unwrap();
 
         Ok(ExternAddInComponentDesc {
             name_override: addin_desc.and_then(|desc| desc.name_override),
+            attach_type,
             init_tkn,
         })
     }
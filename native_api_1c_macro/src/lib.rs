@@ -1,7 +1,7 @@
 mod derive_addin;
 mod extern_functions;
 
-#[proc_macro_derive(AddIn, attributes(add_in, add_in_prop, add_in_func, add_in_con, arg, returns))]
+#[proc_macro_derive(AddIn, attributes(add_in, add_in_prop, add_in_func, add_in_con, add_in_mem, arg, returns))]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_addin::derive(input)
 }
@@ -3,10 +3,16 @@ use std::sync::Arc;
 use native_api_1c::native_api_1c_core::{
     ffi::{
         connection::Connection,
+        provided_types::Tm,
     },
-    interface::{AddInWrapper, ParamValue, ParamValues},
-    widestring::U16CString,
+    interface::{
+        AddInWrapper, DecimalError, ParamValue, ParamValueRef, ParamValues,
+        ParamValuesRef,
+    },
+    rust_decimal::Decimal,
+    widestring::{U16CString, U16Str, U16String},
 };
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
 use native_api_1c_macro::AddIn;
 use rstest::{fixture, rstest};
 
@@ -47,6 +53,17 @@ struct TestAddIn {
     pub out_function: fn(&mut String),
 }
 
+#[derive(AddIn)]
+struct ErrorAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = FUNCTION_NAME_EN, name_ru = FUNCTION_NAME_RU)]
+    #[arg(ty = Int)]
+    #[returns(ty = Int, result)]
+    pub function: fn(i32) -> Result<i32, String>,
+}
+
 #[fixture]
 fn add_in() -> TestAddIn {
     TestAddIn {
@@ -74,7 +91,14 @@ fn test_get_n_methods(add_in: TestAddIn) {
 #[case(PROCEDURE_NAME_RU, Some(1))]
 #[case(OUT_FUNCTION_NAME_EN, Some(2))]
 #[case(OUT_FUNCTION_NAME_RU, Some(2))]
+#[case("function", Some(0))]
+#[case("FUNCTION", Some(0))]
+#[case("функция", Some(0))]
+#[case("ФУНКЦИЯ", Some(0))]
+#[case("outFUNCTION", Some(2))]
+#[case("выводФУНКЦИЯ", Some(2))]
 #[case(INVALID_NAME, None)]
+#[case("Functio", None)]
 fn test_find_method(add_in: TestAddIn, #[case] name: &str, #[case] expected: Option<usize>) {
     assert_eq!(add_in.find_method(&U16CString::from_str_truncate(name)), expected);
 }
@@ -98,7 +122,7 @@ fn test_get_method_name(
 ) {
     assert_eq!(
         add_in.get_method_name(method_i, alias_i).map(|s| s.to_ucstring()),
-        expected.map(|s| U16CString::from_str_truncate(s))
+        expected.map(U16CString::from_str_truncate)
     );
 }
 
@@ -118,7 +142,7 @@ fn test_get_n_params(add_in: TestAddIn, #[case] method_i: usize, #[case] n_param
 #[case(1, 0, None)]
 #[case(1, 1, Some(ParamValue::I32(DEFAULT_VALUE)))]
 #[case(1, 42, None)]
-#[case(2, 0, Some(ParamValue::String(U16CString::from_str_truncate(OUT_STR))))]
+#[case(2, 0, Some(ParamValue::String(U16String::from_str(OUT_STR))))]
 #[case(2, 42, None)]
 #[case(3, 0, None)]
 fn test_get_param_def_value(
@@ -151,6 +175,7 @@ fn test_call_function(mut add_in: TestAddIn) {
 
     let result = add_in.call_as_proc(0, &mut params);
     assert!(result.is_ok());
+    assert_eq!(params.iter_modified().count(), 0);
 }
 
 #[rstest]
@@ -169,7 +194,7 @@ fn test_call_procedure(mut add_in: TestAddIn) {
 
 #[rstest]
 fn test_call_out_function(mut add_in: TestAddIn) {
-    let out_str = U16CString::from_str_truncate("1C");
+    let out_str = U16String::from_str("1C");
     let mut params = ParamValues::new(vec![ParamValue::String(out_str)]);
 
     let result = add_in.call_as_func(2, &mut params);
@@ -177,5 +202,354 @@ fn test_call_out_function(mut add_in: TestAddIn) {
 
     let result = add_in.call_as_proc(2, &mut params);
     assert!(result.is_ok());
-    assert_eq!(params[0], ParamValue::String(U16CString::from_str_truncate("Hello, 1C!")));
+    assert_eq!(params[0], ParamValue::String(U16String::from_str("Hello, 1C!")));
+    assert!(params.is_modified(0));
+}
+
+#[rstest]
+fn test_call_out_function_keeps_nul(mut add_in: TestAddIn) {
+    let out_str = U16String::from_str("1\0C");
+    let mut params = ParamValues::new(vec![ParamValue::String(out_str)]);
+
+    let result = add_in.call_as_proc(2, &mut params);
+    assert!(result.is_ok());
+    assert_eq!(params[0], ParamValue::String(U16String::from_str("Hello, 1\0C!")));
+}
+
+#[rstest]
+fn test_call_error() {
+    let mut add_in = ErrorAddIn {
+        connection: Arc::new(None),
+        function: |a| Err(format!("bad value: {a}")),
+    };
+
+    let mut params = ParamValues::new(vec![ParamValue::I32(1)]);
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(result.unwrap_err().description(), "bad value: 1");
+
+    let mut params = ParamValues::new(vec![ParamValue::from_str("1")]);
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(
+        result.unwrap_err().description(),
+        "invalid value of parameter #1"
+    );
+
+    let result = add_in.call_as_func(1, &mut params);
+    assert_eq!(
+        result.unwrap_err().description(),
+        "method #2 not found"
+    );
+}
+
+#[derive(AddIn)]
+struct LongAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = FUNCTION_NAME_EN, name_ru = FUNCTION_NAME_RU)]
+    #[arg(ty = Long)]
+    #[returns(ty = ULong)]
+    pub function: fn(i64) -> u64,
+}
+
+#[rstest]
+#[case(ParamValue::I32(-7), Some(ParamValue::U64(7)))]
+#[case(ParamValue::I64(i64::MIN), Some(ParamValue::U64(1 << 63)))]
+#[case(ParamValue::F64(4_000_000_000_000.0), Some(ParamValue::U64(4_000_000_000_000)))]
+#[case(ParamValue::F64(1.5), None)]
+#[case(ParamValue::F64(1e19), None)]
+#[case(ParamValue::U64(u64::MAX), None)]
+fn test_call_long(#[case] arg: ParamValue, #[case] expected: Option<ParamValue>) {
+    let mut add_in = LongAddIn {
+        connection: Arc::new(None),
+        function: i64::unsigned_abs,
+    };
+
+    let mut params = ParamValues::new(vec![arg]);
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(result.ok(), expected);
+}
+
+#[derive(AddIn)]
+struct DecimalAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = FUNCTION_NAME_EN, name_ru = FUNCTION_NAME_RU)]
+    #[arg(ty = Decimal)]
+    #[returns(ty = Decimal)]
+    pub function: fn(Decimal) -> Decimal,
+}
+
+#[rstest]
+#[case(ParamValue::from_str("1234.56"), Ok("2469.12"))]
+#[case(ParamValue::F64(0.1), Ok("0.2"))]
+#[case(ParamValue::I64(-5), Ok("-10"))]
+#[case(ParamValue::from_str("1e3"), Ok("2000"))]
+#[case(ParamValue::from_str("1234,56"), Err(DecimalError::Invalid("1234,56".to_string())))]
+#[case(
+    ParamValue::from_str("1e40"),
+    Err(DecimalError::Overflow("1e40".to_string()))
+)]
+#[case(
+    ParamValue::F64(1e30),
+    Err(DecimalError::Overflow("1000000000000000000000000000000".to_string()))
+)]
+#[case(ParamValue::Bool(true), Err(DecimalError::UnexpectedType))]
+fn test_call_decimal(#[case] arg: ParamValue, #[case] expected: Result<&str, DecimalError>) {
+    let mut add_in = DecimalAddIn {
+        connection: Arc::new(None),
+        function: |a| a * Decimal::TWO,
+    };
+
+    let mut params = ParamValues::new(vec![arg]);
+    let result = add_in.call_as_func(0, &mut params);
+    match expected {
+        Ok(expected) => assert_eq!(result.unwrap(), ParamValue::from_str(expected)),
+        Err(err) => assert_eq!(
+            result.unwrap_err().description(),
+            format!("invalid value of parameter #1: {err}")
+        ),
+    }
+}
+
+#[derive(AddIn)]
+struct DateAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = FUNCTION_NAME_EN, name_ru = FUNCTION_NAME_RU)]
+    #[arg(ty = Date)]
+    #[returns(ty = Date)]
+    pub function: fn(NaiveDateTime) -> NaiveDateTime,
+
+    #[add_in_func(name = PROCEDURE_NAME_EN, name_ru = PROCEDURE_NAME_RU)]
+    #[arg(ty = OptionalDate)]
+    #[returns(ty = OptionalDate)]
+    pub optional_function: fn(Option<NaiveDateTime>) -> Option<NaiveDateTime>,
+}
+
+fn date_add_in() -> DateAddIn {
+    DateAddIn {
+        connection: Arc::new(None),
+        function: |date| date,
+        optional_function: |date| date.map(|date| date + chrono::Duration::days(1)),
+    }
+}
+
+#[rstest]
+#[case(Tm::EMPTY_DATE, Some(Tm::EMPTY_DATE))]
+#[case(Tm { year: 9999 - 1900, mon: 11, mday: 31, ..Tm::EMPTY_DATE }, Some(Tm { year: 9999 - 1900, mon: 11, mday: 31, wday: 5, yday: 364, ..Tm::EMPTY_DATE }))]
+#[case(Tm { year: 10000 - 1900, ..Tm::EMPTY_DATE }, None)]
+#[case(Tm { mon: 1, mday: 30, ..Tm::EMPTY_DATE }, None)]
+fn test_call_date(#[case] arg: Tm, #[case] expected: Option<Tm>) {
+    let mut add_in = date_add_in();
+
+    let mut params = ParamValues::new(vec![ParamValue::Date(arg)]);
+    let result = add_in.call_as_func(0, &mut params);
+    match expected {
+        Some(expected) => assert_eq!(result.unwrap(), ParamValue::Date(expected)),
+        None => assert!(result
+            .unwrap_err()
+            .description()
+            .contains("is invalid or out of range")),
+    }
+}
+
+#[test]
+fn test_call_optional_date() {
+    let mut add_in = date_add_in();
+
+    let mut params = ParamValues::new(vec![ParamValue::Date(Tm::EMPTY_DATE)]);
+    let result = add_in.call_as_func(1, &mut params);
+    assert_eq!(result.unwrap(), ParamValue::Date(Tm::EMPTY_DATE));
+
+    let date = NaiveDate::from_ymd_opt(1800, 2, 28).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    let mut params = ParamValues::new(vec![ParamValue::from_nullable_date(Some(date))]);
+    let result = add_in.call_as_func(1, &mut params);
+    assert_eq!(
+        result.unwrap().to_nullable_date(),
+        Some(Some(date + chrono::Duration::days(1)))
+    );
+}
+
+#[derive(AddIn)]
+struct DateTzAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = FUNCTION_NAME_EN, name_ru = FUNCTION_NAME_RU)]
+    #[arg(ty = DateTz)]
+    #[returns(ty = DateUtc)]
+    pub function: fn(DateTime<FixedOffset>) -> DateTime<Utc>,
+}
+
+#[test]
+fn test_call_date_tz() {
+    let mut add_in = DateTzAddIn {
+        connection: Arc::new(None),
+        function: |date| date.to_utc(),
+    };
+
+    let date = "2024-05-17T12:00:00+05:00".parse::<DateTime<FixedOffset>>().unwrap();
+    let mut params = ParamValues::new(vec![ParamValue::from_date_tz(date)]);
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(result.unwrap().to_date_utc(), Some(date.to_utc()));
+
+    let mut params = ParamValues::new(vec![ParamValue::Date(Tm { year: 10000 - 1900, ..Tm::EMPTY_DATE })]);
+    let result = add_in.call_as_func(0, &mut params);
+    assert!(result.is_err());
+}
+
+#[derive(AddIn)]
+struct CoercionAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = FUNCTION_NAME_EN, name_ru = FUNCTION_NAME_RU)]
+    #[arg(ty = Float)]
+    #[arg(ty = Bool)]
+    #[returns(ty = Float)]
+    pub function: fn(f64, bool) -> f64,
+
+    #[add_in_func(name = PROCEDURE_NAME_EN, name_ru = PROCEDURE_NAME_RU)]
+    #[arg(ty = Float, coercion = Strict)]
+    #[arg(ty = Int, coercion = Strings)]
+    #[returns(ty = Float)]
+    pub strict_function: fn(f64, i32) -> f64,
+}
+
+#[rstest]
+#[case(0, ParamValue::I32(5), ParamValue::I32(1), Some(5.0))]
+#[case(0, ParamValue::F64(5.5), ParamValue::Bool(true), Some(5.5))]
+#[case(0, ParamValue::I64(5), ParamValue::I32(0), Some(-5.0))]
+#[case(0, ParamValue::from_str("5"), ParamValue::Bool(true), None)]
+#[case(0, ParamValue::F64(5.0), ParamValue::F64(1.0), None)]
+#[case(1, ParamValue::F64(5.0), ParamValue::from_str("2"), Some(10.0))]
+#[case(1, ParamValue::F64(5.0), ParamValue::F64(2.0), Some(10.0))]
+#[case(1, ParamValue::I32(5), ParamValue::I32(2), None)]
+#[case(1, ParamValue::F64(5.0), ParamValue::from_str("2.5"), None)]
+fn test_call_coercion(
+    #[case] method_i: usize,
+    #[case] arg_1: ParamValue,
+    #[case] arg_2: ParamValue,
+    #[case] expected: Option<f64>,
+) {
+    let mut add_in = CoercionAddIn {
+        connection: Arc::new(None),
+        function: |a, positive| if positive { a } else { -a },
+        strict_function: |a, b| a * f64::from(b),
+    };
+
+    let mut params = ParamValues::new(vec![arg_1, arg_2]);
+    let result = add_in.call_as_func(method_i, &mut params);
+    assert_eq!(result.ok(), expected.map(ParamValue::F64));
+}
+
+#[derive(AddIn)]
+struct BorrowedAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = FUNCTION_NAME_EN, name_ru = FUNCTION_NAME_RU)]
+    #[arg(ty = StrRef)]
+    #[arg(ty = BlobRef)]
+    #[arg(ty = Int, as_out)]
+    #[returns(ty = Int)]
+    pub function: fn(&U16Str, &[u8], &mut i32) -> i32,
+}
+
+#[rstest]
+fn test_call_borrowed() {
+    let mut add_in = BorrowedAddIn {
+        connection: Arc::new(None),
+        function: |str, blob, out| {
+            *out = blob.len() as i32;
+            str.len() as i32
+        },
+    };
+
+    let str = U16String::from_str("hello");
+    let blob = [1, 2, 3];
+    let mut params = ParamValuesRef::new(vec![
+        ParamValueRef::String(str.as_ustr().into()),
+        ParamValueRef::Blob(&blob),
+        ParamValueRef::I32(0),
+    ]);
+    let result = add_in.call_as_func_ref(0, &mut params);
+    assert_eq!(result.unwrap().into_param_value(), ParamValue::I32(5));
+    assert!(!params.is_modified(0));
+    assert!(!params.is_modified(1));
+    assert_eq!(params.value(2), Some(ParamValueRef::I32(3)));
+
+    // owned parameters are borrowed for the call
+    let mut params = ParamValues::new(vec![
+        ParamValue::from_str("hi"),
+        ParamValue::Blob(vec![1, 2, 3, 4]),
+        ParamValue::I32(0),
+    ]);
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(result.unwrap(), ParamValue::I32(2));
+    assert_eq!(params.iter_modified().count(), 1);
+    assert!(params.is_modified(2));
+    assert_eq!(params[1], ParamValue::Blob(vec![1, 2, 3, 4]));
+    assert_eq!(params[2], ParamValue::I32(4));
+
+    let mut params = ParamValues::new(vec![
+        ParamValue::Blob(vec![]),
+        ParamValue::Blob(vec![]),
+        ParamValue::I32(0),
+    ]);
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(
+        result.unwrap_err().description(),
+        "invalid value of parameter #1"
+    );
+}
+
+#[derive(AddIn)]
+struct StringAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = FUNCTION_NAME_EN, name_ru = FUNCTION_NAME_RU)]
+    #[arg(ty = Str)]
+    #[arg(ty = Blob)]
+    #[arg(ty = Str, optional = Str("none"))]
+    #[returns(ty = Str)]
+    pub function: fn(String, Vec<u8>, Option<String>) -> String,
+}
+
+#[rstest]
+#[case::none(ParamValueRef::String(U16String::from_str("none").into()), "hi 3 -")]
+#[case::some(ParamValueRef::String(U16String::from_str("1C").into()), "hi 3 1C")]
+#[case::invalid(ParamValueRef::Empty, "")]
+fn test_call_string(#[case] optional: ParamValueRef<'static>, #[case] expected: &str) {
+    let mut add_in = StringAddIn {
+        connection: Arc::new(None),
+        function: |str, blob, optional| {
+            format!("{str} {} {}", blob.len(), optional.as_deref().unwrap_or("-"))
+        },
+    };
+
+    // strings and blobs are converted from borrowed parameters
+    let str = U16String::from_str("hi");
+    let blob = [1, 2, 3];
+    let mut params = ParamValuesRef::new(vec![
+        ParamValueRef::String(str.as_ustr().into()),
+        ParamValueRef::Blob(&blob),
+        optional,
+    ]);
+    let result = add_in.call_as_func_ref(0, &mut params);
+    match expected {
+        "" => assert_eq!(
+            result.unwrap_err().description(),
+            "invalid value of parameter #3"
+        ),
+        expected => assert_eq!(
+            result.unwrap().into_param_value(),
+            ParamValue::from_str(expected)
+        ),
+    }
+    assert_eq!(params.iter_modified().count(), 0);
 }
@@ -59,6 +59,11 @@ fn test_get_n_props(add_in: TestAddIn) {
 #[case(R_PROP_NAME_RU, Some(1))]
 #[case(W_PROP_NAME, Some(2))]
 #[case(W_PROP_NAME_RU, Some(2))]
+#[case("property", Some(0))]
+#[case("PROPERTY", Some(0))]
+#[case("свойство", Some(0))]
+#[case("СВОЙСТВО", Some(0))]
+#[case("свойствоТОЛЬКОзапись", Some(2))]
 #[case(INVALID_PROP_NAME, None)]
 fn test_find_prop(add_in: TestAddIn, #[case] prop_name: &str, #[case] prop_index: Option<usize>) {
     assert_eq!(add_in.find_prop(&U16CString::from_str_truncate(prop_name)), prop_index);
@@ -81,7 +86,7 @@ fn test_get_prop_name(
     #[case] name: Option<&str>,
 ) {
     let prop_name = add_in.get_prop_name(prop_index, name_index);
-    assert_eq!(prop_name.map(|s| s.to_ucstring()), name.map(|s| U16CString::from_str_truncate(s)));
+    assert_eq!(prop_name.map(|s| s.to_ucstring()), name.map(U16CString::from_str_truncate));
 }
 
 #[rstest]
@@ -101,38 +106,71 @@ fn test_is_prop_writable(add_in: TestAddIn, #[case] prop_index: usize, #[case] w
 }
 
 #[rstest]
-#[case(0, Some(START_VALUE))]
-#[case(1, Some(START_VALUE))]
-#[case(2, None)]
+#[case(0, Ok(START_VALUE))]
+#[case(1, Ok(START_VALUE))]
+#[case(2, Err("property #3 not found"))]
 fn test_get_prop_val(
     add_in: TestAddIn,
     #[case] prop_i: usize,
-    #[case] expected_value: Option<i32>,
+    #[case] expected_value: Result<i32, &str>,
 ) {
     let prop_value = add_in.get_prop_val(prop_i);
     match expected_value {
-        Some(value) => {
+        Ok(value) => {
             assert!(prop_value.is_ok());
             assert_eq!(prop_value.unwrap(), ParamValue::I32(value));
         }
-        None => assert!(prop_value.is_err()),
+        Err(description) => assert_eq!(prop_value.unwrap_err().description(), description),
     }
 }
 
 #[rstest]
 #[case(0, Ok(()), |add_in: &TestAddIn| add_in.rw_property, NEW_VALUE)]
-#[case(1, Err(()), |add_in: &TestAddIn| add_in.r_property, START_VALUE)]
+#[case(1, Err("property #2 not found"), |add_in: &TestAddIn| add_in.r_property, START_VALUE)]
 #[case(2, Ok(()), |add_in: &TestAddIn| add_in.w_property, NEW_VALUE)]
 fn test_set_prop_val(
     mut add_in: TestAddIn,
     #[case] prop_i: usize,
-    #[case] expected_result: Result<(), ()>,
+    #[case] expected_result: Result<(), &str>,
     #[case] value_getter: fn(&TestAddIn) -> i32,
     #[case] new_value: i32,
 ) {
     assert_eq!(
-        add_in.set_prop_val(prop_i, ParamValue::I32(NEW_VALUE)),
-        expected_result
+        add_in.set_prop_val(prop_i, ParamValue::I32(NEW_VALUE)).map_err(|err| err.description()),
+        expected_result.map_err(str::to_owned)
     );
     assert_eq!(value_getter(&add_in), new_value);
 }
+
+#[derive(AddIn)]
+#[add_in(coercion = Strings)]
+struct CoercionAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Float, name = RW_PROP_NAME, name_ru = RW_PROP_NAME_RU, readable, writable)]
+    float_property: f64,
+
+    #[add_in_prop(ty = Int, name = W_PROP_NAME, name_ru = W_PROP_NAME_RU, writable, coercion = Strict)]
+    strict_property: i32,
+}
+
+#[rstest]
+#[case(0, ParamValue::I32(NEW_VALUE), true)]
+#[case(0, ParamValue::from_str("24"), true)]
+#[case(0, ParamValue::from_str("24,0"), false)]
+#[case(1, ParamValue::I32(NEW_VALUE), true)]
+#[case(1, ParamValue::F64(24.0), false)]
+#[case(1, ParamValue::from_str("24"), false)]
+fn test_set_prop_val_coercion(#[case] prop_i: usize, #[case] value: ParamValue, #[case] accepted: bool) {
+    let mut add_in = CoercionAddIn {
+        connection: Arc::new(None),
+        float_property: 0.0,
+        strict_property: 0,
+    };
+
+    assert_eq!(add_in.set_prop_val(prop_i, value).is_ok(), accepted);
+    if accepted {
+        assert_eq!(add_in.float_property + f64::from(add_in.strict_property), f64::from(NEW_VALUE));
+    }
+}
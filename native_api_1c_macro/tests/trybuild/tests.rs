@@ -5,34 +5,117 @@ fn trybuild_props() {
     t.pass("tests/trybuild/to_build/props.rs");
 }
 
+#[test]
+fn trybuild_mem_manager() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/mem_manager.rs");
+}
+
+#[test]
+fn trybuild_event_buffer() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/event_buffer.rs");
+}
+
+#[test]
+fn trybuild_capabilities() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/capabilities.rs");
+}
+
+#[test]
+fn trybuild_attach_type() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/attach_type/component.rs");
+    t.pass("tests/trybuild/to_build/attach_type/library.rs");
+    t.compile_fail("tests/trybuild/to_build/attach_type/conflict.rs");
+}
+
+#[test]
+fn trybuild_api_version() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/api_version/v1.rs");
+    t.pass("tests/trybuild/to_build/api_version/windows1251.rs");
+    t.compile_fail("tests/trybuild/to_build/api_version/unknown.rs");
+    t.compile_fail("tests/trybuild/to_build/api_version/unknown_encoding.rs");
+}
+
+#[test]
+fn trybuild_coercion() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/coercion/component.rs");
+    t.compile_fail("tests/trybuild/to_build/coercion/unknown.rs");
+}
+
 #[test]
 fn trybuild_functions() {
     let t = trybuild::TestCases::new();
 
     t.pass("tests/trybuild/to_build/functions/bool_type.rs");
     t.pass("tests/trybuild/to_build/functions/int_type.rs");
+    t.pass("tests/trybuild/to_build/functions/long_type.rs");
+    t.pass("tests/trybuild/to_build/functions/ulong_type.rs");
     t.pass("tests/trybuild/to_build/functions/float_type.rs");
+    t.pass("tests/trybuild/to_build/functions/decimal_type.rs");
     t.pass("tests/trybuild/to_build/functions/str_type.rs");
     t.pass("tests/trybuild/to_build/functions/date_type.rs");
+    t.pass("tests/trybuild/to_build/functions/optional_date_type.rs");
+    t.pass("tests/trybuild/to_build/functions/date_tz_type.rs");
+    t.pass("tests/trybuild/to_build/functions/date_utc_type.rs");
     t.pass("tests/trybuild/to_build/functions/blob_type.rs");
+    t.pass("tests/trybuild/to_build/functions/str_ref_type.rs");
+    t.pass("tests/trybuild/to_build/functions/blob_ref_type.rs");
+    t.pass("tests/trybuild/to_build/functions/platform_buf_type.rs");
 
     t.pass("tests/trybuild/to_build/functions/result/bool_type.rs");
     t.pass("tests/trybuild/to_build/functions/result/int_type.rs");
+    t.pass("tests/trybuild/to_build/functions/result/long_type.rs");
+    t.pass("tests/trybuild/to_build/functions/result/ulong_type.rs");
     t.pass("tests/trybuild/to_build/functions/result/float_type.rs");
     t.pass("tests/trybuild/to_build/functions/result/str_type.rs");
     t.pass("tests/trybuild/to_build/functions/result/date_type.rs");
+    t.pass("tests/trybuild/to_build/functions/result/optional_date_type.rs");
+    t.pass("tests/trybuild/to_build/functions/result/date_utc_type.rs");
     t.pass("tests/trybuild/to_build/functions/result/blob_type.rs");
+    t.pass("tests/trybuild/to_build/functions/result/error_type.rs");
 
     t.pass("tests/trybuild/to_build/functions/out_params/bool_type.rs");
     t.pass("tests/trybuild/to_build/functions/out_params/int_type.rs");
+    t.pass("tests/trybuild/to_build/functions/out_params/long_type.rs");
+    t.pass("tests/trybuild/to_build/functions/out_params/ulong_type.rs");
     t.pass("tests/trybuild/to_build/functions/out_params/float_type.rs");
+    t.pass("tests/trybuild/to_build/functions/out_params/decimal_type.rs");
     t.pass("tests/trybuild/to_build/functions/out_params/str_type.rs");
+    t.pass("tests/trybuild/to_build/functions/out_params/optional_date_type.rs");
+    t.pass("tests/trybuild/to_build/functions/out_params/date_tz_type.rs");
     t.pass("tests/trybuild/to_build/functions/out_params/blob_type.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/out_params/str_ref_type.rs");
 
     t.pass("tests/trybuild/to_build/functions/defaults/bool_type.rs");
     t.pass("tests/trybuild/to_build/functions/defaults/int_type.rs");
+    t.pass("tests/trybuild/to_build/functions/defaults/long_type.rs");
+    t.pass("tests/trybuild/to_build/functions/defaults/ulong_type.rs");
     t.pass("tests/trybuild/to_build/functions/defaults/float_type.rs");
     t.pass("tests/trybuild/to_build/functions/defaults/str_type.rs");
     t.compile_fail("tests/trybuild/to_build/functions/defaults/date_type.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/defaults/optional_date_type.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/defaults/date_tz_type.rs");
     t.compile_fail("tests/trybuild/to_build/functions/defaults/blob_type.rs");
 }
+
+#[test]
+fn trybuild_names() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/trybuild/to_build/names/case_insensitive.rs");
+    t.compile_fail("tests/trybuild/to_build/names/func_collision.rs");
+    t.compile_fail("tests/trybuild/to_build/names/prop_collision.rs");
+    // names, given by paths, are checked at compile time
+    t.compile_fail("tests/trybuild/to_build/names/const_collision.rs");
+}
@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+#[add_in(api_version = V1, pstr_encoding = Cp866)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
+    pub my_procedure: fn(&Self),
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        api_version::{ApiVersion, PStrEncoding},
+        connection::Connection,
+    },
+    interface::AddInWrapper,
+};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+#[add_in(name = "MyAddIn", api_version = V1, pstr_encoding = Windows1251)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(ty = Str)]
+    #[returns(ty = Str)]
+    pub my_function: fn(&Self, String) -> String,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function: |_, arg| arg,
+        }
+    }
+}
+
+fn main() {
+    let add_in = MyAddIn::new();
+    assert_eq!(add_in.get_info(), 1000);
+    assert_eq!(ApiVersion::from_info(add_in.get_info()), ApiVersion::V1);
+    assert_eq!(add_in.pstr_encoding(), PStrEncoding::Windows1251);
+}
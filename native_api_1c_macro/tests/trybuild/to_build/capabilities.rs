@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::{
+    connection::Connection, platform_capabilities, AppCapabilities,
+};
+use native_api_1c_macro::{extern_functions, AddIn};
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
+    pub my_procedure: fn(&Self),
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_procedure: |_| {},
+        }
+    }
+}
+
+extern_functions! {
+    #![add_in_library(capabilities = V2)]
+
+    MyAddIn::new(),
+}
+
+fn main() {
+    assert_eq!(platform_capabilities(), AppCapabilities::Invalid);
+
+    assert_eq!(SetPlatformCapabilities(3), AppCapabilities::V2);
+    assert_eq!(platform_capabilities(), AppCapabilities::V2);
+
+    assert_eq!(SetPlatformCapabilities(1), AppCapabilities::V2);
+    assert_eq!(platform_capabilities(), AppCapabilities::V1);
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+#[add_in(coercion = Strings)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Float, name = "MyProp", name_ru = "МоеСвойство", readable, writable, coercion = Numeric)]
+    pub my_prop: f64,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(ty = Float)]
+    #[arg(ty = Int, coercion = Strict)]
+    #[arg(ty = Bool, coercion = "Numeric")]
+    #[returns(ty = Float)]
+    pub my_function: fn(&Self, f64, i32, bool) -> f64,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_prop: 0.0,
+            my_function: Self::my_function_inner,
+        }
+    }
+
+    fn my_function_inner(&self, a: f64, b: i32, negate: bool) -> f64 {
+        let sum = a + f64::from(b) + self.my_prop;
+        if negate { -sum } else { sum }
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(ty = Float, coercion = Lenient)]
+    #[returns(ty = Float)]
+    pub my_function: fn(f64) -> f64,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function: |a| a,
+        }
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
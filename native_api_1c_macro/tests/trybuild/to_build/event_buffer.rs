@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::connection::Connection,
+    interface::{EventBuffer, EventBufferConfig},
+};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+#[add_in(name = "Scanner", event_buffer_depth = 100)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "Notify", name_ru = "Оповестить")]
+    #[arg(ty = Str)]
+    #[returns(ty = Bool)]
+    pub notify: fn(&Self, String) -> bool,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            notify: Self::notify_inner,
+        }
+    }
+
+    fn notify_inner(&self, data: String) -> bool {
+        let Some(connection) = *self.connection else {
+            return false;
+        };
+        let events = EventBuffer::new(
+            connection,
+            EventBufferConfig {
+                max_depth: Some(1000),
+                ..Default::default()
+            },
+        );
+        let sent = events.send("Scanner", "Barcode", &data);
+        events.stats().dropped == 0 && sent
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(ty = BlobRef)]
+    #[returns(ty = Int)]
+    pub my_function: fn(&Self, &[u8]) -> i32,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function: Self::my_function_inner,
+        }
+    }
+
+    fn my_function_inner(&self, arg: &[u8]) -> i32 {
+        arg.iter().map(|b| *b as i32).sum()
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunctionMut", name_ru = "МояФункцияМут")]
+    #[arg(ty = DateTz, as_in)]
+    #[arg(ty = DateUtc, as_out)]
+    #[returns(ty = Bool)]
+    pub my_function_mut: fn(&mut Self, chrono::DateTime<chrono::FixedOffset>, &mut chrono::DateTime<chrono::Utc>) -> bool,
+
+    #[add_in_func(name = "MyFunctionRef", name_ru = "МояФункцияРеф")]
+    #[arg(ty = DateTz, as_in)]
+    #[arg(ty = DateUtc, as_out)]
+    #[returns(ty = Bool)]
+    pub my_function_ref: fn(&Self, chrono::DateTime<chrono::FixedOffset>, &mut chrono::DateTime<chrono::Utc>) -> bool,
+
+    #[add_in_func(name = "MyFunctionNoRef", name_ru = "МояФункцияБезРеф")]
+    #[arg(ty = DateTz, as_in)]
+    #[arg(ty = DateUtc, as_out)]
+    #[returns(ty = Bool)]
+    pub my_function_no_ref: fn(chrono::DateTime<chrono::FixedOffset>, &mut chrono::DateTime<chrono::Utc>) -> bool,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function_mut: Self::my_function_mut_inner,
+            my_function_ref: Self::my_function_ref_inner,
+            my_function_no_ref: Self::my_function_no_ref_inner,
+        }
+    }
+
+    fn my_function_mut_inner(&mut self, in_arg: chrono::DateTime<chrono::FixedOffset>, out_arg: &mut chrono::DateTime<chrono::Utc>) -> bool {
+        *out_arg = in_arg.to_utc();
+        true
+    }
+
+    fn my_function_ref_inner(&self, in_arg: chrono::DateTime<chrono::FixedOffset>, out_arg: &mut chrono::DateTime<chrono::Utc>) -> bool {
+        *out_arg = in_arg.to_utc();
+        true
+    }
+
+    fn my_function_no_ref_inner(in_arg: chrono::DateTime<chrono::FixedOffset>, out_arg: &mut chrono::DateTime<chrono::Utc>) -> bool {
+        *out_arg = in_arg.to_utc();
+        true
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{ffi::connection::Connection, widestring::U16Str};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(ty = StrRef, as_out)]
+    pub my_function: fn(&Self, &U16Str),
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function: Self::my_function_inner,
+        }
+    }
+
+    fn my_function_inner(&self, _arg: &U16Str) {}
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
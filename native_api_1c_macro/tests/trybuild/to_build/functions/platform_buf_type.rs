@@ -0,0 +1,60 @@
+use std::{fmt::Write as _, io::Write as _, sync::Arc};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        memory_manager::MemoryManager,
+        platform_buf::{PlatformBuf, PlatformStr},
+    },
+    interface::AddInError,
+};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_mem]
+    memory_manager: Option<&'static MemoryManager>,
+
+    #[add_in_func(name = "Report", name_ru = "Отчет")]
+    #[arg(ty = Int)]
+    #[returns(ty = StrBuf, result)]
+    pub report: fn(&Self, i32) -> Result<PlatformStr, AddInError>,
+
+    #[add_in_func(name = "File", name_ru = "Файл")]
+    #[returns(ty = BlobBuf, result)]
+    pub file: fn(&Self) -> Result<PlatformBuf, AddInError>,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            memory_manager: None,
+            report: Self::report_inner,
+            file: Self::file_inner,
+        }
+    }
+
+    fn report_inner(&self, rows: i32) -> Result<PlatformStr, AddInError> {
+        let mem = self.memory_manager.ok_or("no memory manager")?;
+        let mut report = PlatformStr::new(mem);
+        for row in 0..rows {
+            writeln!(report, "{row}").map_err(|_| "out of memory")?;
+        }
+        Ok(report)
+    }
+
+    fn file_inner(&self) -> Result<PlatformBuf, AddInError> {
+        let mem = self.memory_manager.ok_or("no memory manager")?;
+        let mut buf = PlatformBuf::with_capacity(mem, 3)?;
+        buf.write_all(&[1, 2, 3]).map_err(|_| "out of memory")?;
+        Ok(buf)
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::connection::Connection,
+    interface::AddInError,
+};
+use native_api_1c_macro::AddIn;
+
+pub struct MyError(String);
+
+impl From<MyError> for AddInError {
+    fn from(err: MyError) -> Self {
+        AddInError::new(err.0)
+    }
+}
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(ty = Int)]
+    #[returns(ty = Int, result)]
+    pub my_function: fn(&Self, i32) -> Result<i32, MyError>,
+
+    #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
+    #[arg(ty = Int)]
+    #[returns(result)]
+    pub my_procedure: fn(&Self, i32) -> Result<(), String>,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function: Self::my_function_inner,
+            my_procedure: Self::my_procedure_inner,
+        }
+    }
+
+    fn my_function_inner(&self, arg: i32) -> Result<i32, MyError> {
+        if arg < 0 {
+            return Err(MyError(format!("negative argument: {arg}")));
+        }
+        Ok(arg)
+    }
+
+    fn my_procedure_inner(&self, arg: i32) -> Result<(), String> {
+        if arg < 0 {
+            return Err("negative argument".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::{
+    connection::Connection,
+    memory_manager::MemoryManager,
+};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_mem]
+    memory_manager: Arc<Option<&'static MemoryManager>>,
+
+    #[add_in_func(name = "SaveSetting", name_ru = "СохранитьНастройку")]
+    #[arg(ty = Str)]
+    #[returns(ty = Bool)]
+    pub save_setting: fn(&Self, String) -> bool,
+}
+
+#[derive(AddIn)]
+pub struct MyOtherAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_mem]
+    memory_manager: Option<&'static MemoryManager>,
+
+    #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
+    pub my_procedure: fn(&Self),
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            memory_manager: Arc::new(None),
+            save_setting: Self::save_setting_inner,
+        }
+    }
+
+    fn save_setting_inner(&self, value: String) -> bool {
+        let (Some(connection), Some(mem)) = (*self.connection, *self.memory_manager) else {
+            return false;
+        };
+        connection.register_profile_as("MyAddIn")
+            && connection.write(
+                mem,
+                "Setting",
+                &native_api_1c::native_api_1c_core::interface::ParamValue::from_str(value),
+            )
+    }
+}
+
+impl MyOtherAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            memory_manager: None,
+            my_procedure: |_| {},
+        }
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+    let _other_add_in = MyOtherAddIn::new();
+}
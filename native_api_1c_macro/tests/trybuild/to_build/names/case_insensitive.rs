@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Int, name = "Counter", name_ru = "Счётчик", readable)]
+    pub counter: i32,
+
+    #[add_in_prop(ty = Int, name = "Count", name_ru = "Счет", readable)]
+    pub count: i32,
+
+    #[add_in_func(name = "Reset", name_ru = "Reset")]
+    pub reset: fn(&mut Self),
+}
+
+fn main() {
+    let _add_in = MyAddIn {
+        connection: Arc::new(None),
+        counter: 0,
+        count: 0,
+        reset: |add_in| add_in.counter = 0,
+    };
+}
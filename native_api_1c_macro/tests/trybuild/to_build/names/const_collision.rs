@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+const COUNTER_NAME: &str = "Counter";
+const OTHER_COUNTER_NAME: &str = "COUNTER";
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Int, name = COUNTER_NAME, name_ru = "Счётчик", readable)]
+    pub counter: i32,
+
+    #[add_in_prop(ty = Int, name = OTHER_COUNTER_NAME, name_ru = "ДругойСчётчик", readable)]
+    pub other_counter: i32,
+}
+
+fn main() {
+    let _add_in = MyAddIn {
+        connection: Arc::new(None),
+        counter: 0,
+        other_counter: 0,
+    };
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    pub my_function: fn(&Self),
+
+    #[add_in_func(name = "OtherFunction", name_ru = "мояфункция")]
+    pub other_function: fn(&Self),
+
+    #[add_in_func(name = "MYFUNCTION", name_ru = "ТретьяФункция")]
+    pub third_function: fn(&Self),
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function: Self::my_function_inner,
+            other_function: Self::my_function_inner,
+            third_function: Self::my_function_inner,
+        }
+    }
+
+    fn my_function_inner(&self) {}
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
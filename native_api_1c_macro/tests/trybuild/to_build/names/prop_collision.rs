@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Int, name = "Счётчик", name_ru = "Счётчик", readable)]
+    pub counter: i32,
+
+    #[add_in_prop(ty = Int, name = "СЧЁТЧИК", name_ru = "ДругойСчётчик", readable)]
+    pub other_counter: i32,
+}
+
+fn main() {
+    let _add_in = MyAddIn {
+        connection: Arc::new(None),
+        counter: 0,
+        other_counter: 0,
+    };
+}
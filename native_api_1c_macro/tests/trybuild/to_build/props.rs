@@ -42,6 +42,10 @@ pub struct MyAddIn {
     pub date_prop_r: chrono::NaiveDateTime,
     #[add_in_prop(ty = Date, name = "prp_W_date", name_ru = "свств_W_date", writable)]
     pub date_prop_w: chrono::NaiveDateTime,
+    #[add_in_prop(ty = OptionalDate, name = "prp_RW_optional_date", name_ru = "свств_RW_optional_date", readable, writable)]
+    pub optional_date_prop_rw: Option<chrono::NaiveDateTime>,
+    #[add_in_prop(ty = DateUtc, name = "prp_RW_date_utc", name_ru = "свств_RW_date_utc", readable, writable)]
+    pub date_utc_prop_rw: chrono::DateTime<chrono::Utc>,
 
     #[add_in_prop(ty = Blob, name = "prp_RW_blob", name_ru = "свств_RW_blob", readable, writable)]
     pub blob_prop_rw: Vec<u8>,
@@ -70,6 +74,8 @@ impl MyAddIn {
             date_prop_rw: chrono::NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
             date_prop_r: chrono::NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
             date_prop_w: chrono::NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
+            optional_date_prop_rw: None,
+            date_utc_prop_rw: chrono::DateTime::UNIX_EPOCH,
             blob_prop_rw: Vec::new(),
             blob_prop_r: Vec::new(),
             blob_prop_w: Vec::new(),
@@ -62,7 +62,7 @@ impl SampleAddIn {
             + self.some_prop
             + arg
             + self.private_field
-            + arg_maybe_default.unwrap_or_default() as i32)
+            + arg_maybe_default.unwrap_or_default())
     }
 
     fn my_procedure_inner(&mut self) {